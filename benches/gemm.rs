@@ -0,0 +1,59 @@
+extern crate mtrs;
+
+use mtrs::*;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+fn square(size: usize) -> Matrix<f64> {
+    Matrix::from_vec(
+        size,
+        (0..size * size)
+            .map(|i| ((i * 7919) % 101) as f64 / 101.0)
+            .collect(),
+    )
+}
+
+// The flat ikj loop that the tiled kernel replaced, as a baseline to compare it against
+fn ikj(a: &Matrix<f64>, b: &Matrix<f64>) -> Matrix<f64> {
+    let ((height, depth), width) = (a.size(), b.size().1);
+    let (left, right) = (a.as_slice(), b.as_slice());
+    let mut body = vec![0.0; height * width];
+
+    for (row, out) in body.chunks_exact_mut(width).enumerate() {
+        for (k, &entry) in left[row * depth..(row + 1) * depth].iter().enumerate() {
+            for (sum, &other) in out.iter_mut().zip(&right[k * width..(k + 1) * width]) {
+                *sum += entry * other;
+            }
+        }
+    }
+
+    Matrix::from_vec((height, width), body)
+}
+
+fn benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gemm");
+    group.sample_size(10);
+
+    for &size in &[16, 64, 256, 500] {
+        let (a, b) = (square(size), square(size));
+        let mut out = Matrix::zeros(size);
+
+        group.bench_with_input(BenchmarkId::new("mul", size), &size, |bench, _| {
+            bench.iter(|| black_box(&a * &b))
+        });
+        group.bench_with_input(BenchmarkId::new("ikj", size), &size, |bench, _| {
+            bench.iter(|| ikj(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("gemm", size), &size, |bench, _| {
+            bench.iter(|| gemm(1.0, black_box(&a), black_box(&b), 0.0, &mut out))
+        });
+        group.bench_with_input(BenchmarkId::new("strassen", size), &size, |bench, _| {
+            bench.iter(|| black_box(&a).mul_strassen(black_box(&b)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, benchmark);
+criterion_main!(benches);
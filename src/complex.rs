@@ -0,0 +1,263 @@
+use crate::{Element, Matrix, MatrixError};
+
+use num_complex::ComplexFloat;
+use num_traits::{Float, Zero};
+
+impl<T: ComplexFloat + Element> Matrix<T> {
+    /// The matrix with every entry replaced by its complex conjugate
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let matrix = Matrix::from_vec((1, 2), vec![Complex::new(1.0, 2.0), Complex::new(3.0, 0.0)]);
+    ///
+    /// assert_eq!(
+    ///     matrix.conjugate(),
+    ///     Matrix::from_vec((1, 2), vec![Complex::new(1.0, -2.0), Complex::new(3.0, 0.0)])
+    /// );
+    /// ```
+    pub fn conjugate(&self) -> Self {
+        Self::from_vec(
+            (self.height, self.width),
+            self.data.iter().map(|x| x.conj()).collect(),
+        )
+    }
+
+    /// The adjoint (conjugate transpose) of the matrix, `Aᴴ`. For real matrices this is the
+    /// transpose.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let i = Complex::new(0.0, 1.0);
+    /// let matrix = Matrix::from_vec((1, 2), vec![i, 2.0 + i]);
+    ///
+    /// assert_eq!(matrix.adjoint(), Matrix::from_vec((2, 1), vec![-i, 2.0 - i]));
+    /// ```
+    pub fn adjoint(&self) -> Self {
+        let mut adjoint = self.conjugate();
+        adjoint.transpose();
+        adjoint
+    }
+
+    /// The conjugate transpose of the matrix, the same as `Matrix::adjoint`
+    pub fn conjugate_transpose(&self) -> Self {
+        self.adjoint()
+    }
+
+    /// Whether the matrix is Hermitian, that is square and equal to its own adjoint. For real
+    /// matrices this is whether the matrix is symmetric.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let i = Complex::new(0.0, 1.0);
+    /// let one = Complex::new(1.0, 0.0);
+    ///
+    /// assert!(Matrix::from_vec(2, vec![one, i, -i, one]).is_hermitian());
+    /// assert!(!Matrix::from_vec(2, vec![one, i, i, one]).is_hermitian());
+    /// ```
+    pub fn is_hermitian(&self) -> bool {
+        let n = self.height;
+        self.width == n
+            && (0..n).all(|i| (i..n).all(|j| self.data[i * n + j] == self.data[j * n + i].conj()))
+    }
+
+    /// The 1-norm of the matrix, the largest sum of the moduli in a column
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let matrix = Matrix::from_vec(2, vec![
+    ///     Complex::new(3.0, 4.0), Complex::new(1.0, 0.0),
+    ///     Complex::new(0.0, -2.0), Complex::new(0.0, 1.0),
+    /// ]);
+    ///
+    /// assert_eq!(matrix.norm1(), 7.0);
+    /// assert_eq!(matrix.norm_inf(), 6.0);
+    /// ```
+    pub fn norm1(&self) -> T::Real {
+        (0..self.width)
+            .map(|col| {
+                (0..self.height).fold(T::Real::zero(), |acc, row| {
+                    acc + self.data[row * self.width + col].abs()
+                })
+            })
+            .fold(T::Real::zero(), Float::max)
+    }
+
+    /// The infinity norm of the matrix, the largest sum of the moduli in a row. This is the same
+    /// as the 1-norm for Hermitian matrices.
+    pub fn norm_inf(&self) -> T::Real {
+        self.data
+            .chunks(self.width.max(1))
+            .map(|row| row.iter().fold(T::Real::zero(), |acc, x| acc + x.abs()))
+            .fold(T::Real::zero(), Float::max)
+    }
+
+    /// The Frobenius norm of the matrix, `sqrt(tr(AᴴA))`, which is the square root of the sum of
+    /// the squared moduli of the entries
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let matrix = Matrix::from_vec((1, 2), vec![Complex::new(3.0, 4.0), Complex::new(0.0, 0.0)]);
+    ///
+    /// assert_eq!(matrix.norm_frobenius(), 5.0);
+    /// ```
+    pub fn norm_frobenius(&self) -> T::Real {
+        self.data
+            .iter()
+            .fold(T::Real::zero(), |acc, x| acc.hypot(x.abs()))
+    }
+
+    /// The spectral norm of the matrix, its largest singular value. For Hermitian matrices this is
+    /// the largest modulus of an eigenvalue.
+    ///
+    /// It is the 2-norm of the real embedding `[Re A, -Im A; Im A, Re A]`, which has the singular
+    /// values of `A`, each twice, so the real SVD serves complex matrices too.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Complex, Matrix};
+    ///
+    /// let matrix = Matrix::from_vec((1, 2), vec![Complex::new(3.0, 0.0), Complex::new(0.0, 4.0)]);
+    ///
+    /// assert!((matrix.norm_spectral().unwrap() - 5.0f64).abs() < 1e-12);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the SVD of the embedding does not converge
+    pub fn norm_spectral(&self) -> Result<T::Real, MatrixError>
+    where
+        T::Real: Element,
+    {
+        let (m, n) = self.size();
+        let mut embedding = Matrix::zeros((2 * m, 2 * n));
+        for row in 0..m {
+            for col in 0..n {
+                let entry = self.data[row * n + col];
+                let (re, im) = (entry.re(), entry.im());
+                embedding.data[row * 2 * n + col] = re;
+                embedding.data[row * 2 * n + n + col] = -im;
+                embedding.data[(m + row) * 2 * n + col] = im;
+                embedding.data[(m + row) * 2 * n + n + col] = re;
+            }
+        }
+
+        embedding.norm2()
+    }
+}
+
+#[cfg(test)]
+mod complex_tests {
+    use crate::Matrix;
+
+    use num_complex::Complex;
+
+    fn hermitian() -> Matrix<Complex<f64>> {
+        Matrix::from_vec(
+            2,
+            vec![
+                Complex::new(2.0, 0.0),
+                Complex::new(1.0, -1.0),
+                Complex::new(1.0, 1.0),
+                Complex::new(3.0, 0.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_adjoint() {
+        let a = Matrix::from_vec(
+            (2, 3),
+            vec![
+                Complex::new(1.0, 1.0),
+                Complex::new(2.0, 0.0),
+                Complex::new(0.0, -3.0),
+                Complex::new(4.0, 2.0),
+                Complex::new(0.0, 0.0),
+                Complex::new(-1.0, 1.0),
+            ],
+        );
+        let adjoint = a.adjoint();
+
+        assert_eq!(adjoint.size(), (3, 2));
+        assert_eq!(adjoint[(2, 0)], Complex::new(0.0, 3.0));
+        assert_eq!(adjoint.adjoint(), a);
+        assert_eq!(a.conjugate_transpose(), adjoint);
+
+        // `AAᴴ` is always Hermitian
+        assert!(!a.is_hermitian());
+        assert!((a.clone() * adjoint).is_hermitian());
+    }
+
+    #[test]
+    fn test_hermitian() {
+        let mut not_real_diagonal = hermitian();
+        not_real_diagonal
+            .set((0, 0), Complex::new(2.0, 1.0))
+            .unwrap();
+
+        assert!(hermitian().is_hermitian());
+        assert_eq!(hermitian().adjoint(), hermitian());
+        assert!(!not_real_diagonal.is_hermitian());
+        assert!(!Matrix::<Complex<f64>>::zeros((2, 3)).is_hermitian());
+        assert!(Matrix::from_vec(2, vec![1.0, 2.0, 2.0, 1.0]).is_hermitian());
+    }
+
+    #[test]
+    fn test_norms() {
+        let h = hermitian();
+        let root_two = 2f64.sqrt();
+
+        assert!((h.norm1() - (3.0 + root_two)).abs() < 1e-12);
+        assert_eq!(h.norm1(), h.norm_inf());
+        assert!((h.norm_frobenius() - 17f64.sqrt()).abs() < 1e-12);
+
+        // Real matrices measure the same as their complex embedding
+        let real = Matrix::from_vec(2, vec![1.0, -2.0, 3.0, 4.0]);
+        assert_eq!(real.norm1(), 6.0);
+        assert_eq!(real.norm_inf(), 7.0);
+        assert!((real.norm_frobenius() - 30f64.sqrt()).abs() < 1e-12);
+        assert_eq!(Matrix::<f64>::zeros(0).norm_inf(), 0.0);
+    }
+
+    #[test]
+    fn test_spectral_norm() {
+        // The eigenvalues of `h` are `1` and `4`
+        let rotated = hermitian().scalar_mul(Complex::new(0.0, -1.0));
+        assert!((hermitian().norm_spectral().unwrap() - 4.0).abs() < 1e-12);
+        assert!((rotated.norm_spectral().unwrap() - 4.0).abs() < 1e-12);
+
+        let wide = Matrix::from_vec(
+            (1, 2),
+            vec![Complex::new(3.0, 0.0), Complex::new(0.0, 4.0f64)],
+        );
+        assert!((wide.norm_spectral().unwrap() - 5.0).abs() < 1e-12);
+
+        let real = Matrix::from_vec((2, 3), vec![1.0, -2.0, 3.0, 4.0, 0.5, -1.0f64]);
+        assert!((real.norm_spectral().unwrap() - real.norm2().unwrap()).abs() < 1e-12);
+        assert_eq!(
+            Matrix::<Complex<f64>>::zeros((0, 3)).norm_spectral(),
+            Ok(0.0)
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let matrix = Matrix::from_vec(
+            2,
+            vec![
+                Complex::new(1.0, 2.0),
+                Complex::new(0.0, 0.0),
+                Complex::new(-10.5, 1.0),
+                Complex::new(3.0, -4.0),
+            ],
+        );
+
+        assert_eq!(format!("{}", matrix), "    1+2i 0+0i \n-10.5+1i 3-4i \n");
+        assert_eq!(
+            format!("{:.1}", matrix),
+            "  1.0+2.0i 0.0+0.0i \n-10.5+1.0i 3.0-4.0i \n"
+        );
+    }
+}
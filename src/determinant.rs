@@ -0,0 +1,304 @@
+use std::ops::Neg;
+
+use crate::parallel::{self, Element};
+
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+#[cfg(feature = "complex")]
+use num_traits::Float;
+use num_traits::Num;
+
+/// Element types that a `Matrix` knows how to calculate the determinant of.
+///
+/// The provided methods use the fraction-free Bareiss algorithm, where every intermediate division
+/// is exact, so the result is exact as long as no intermediate value overflows. They suit any exact
+/// `Num` type: integers, and user defined rationals or big integers, which only need an empty
+/// `impl Determinant for MyType {}`. Primitive integers also detect overflow in
+/// `checked_determinant`, and unsigned integers detect it in `determinant` too, since any negative
+/// intermediate value overflows them. Floats (and, with the `complex` feature, complex floats) use
+/// Gaussian elimination with partial pivoting.
+/// ```
+/// use mtrs::{Determinant, Matrix};
+/// use std::ops::{Add, Div, Mul, Rem, Sub};
+///
+/// // A user defined integer type, without any of the primitive integer impls
+/// #[derive(Clone, Copy, PartialEq, Debug)]
+/// struct Int(i64);
+/// # impl Add for Int { type Output = Int; fn add(self, o: Int) -> Int { Int(self.0 + o.0) } }
+/// # impl Sub for Int { type Output = Int; fn sub(self, o: Int) -> Int { Int(self.0 - o.0) } }
+/// # impl Mul for Int { type Output = Int; fn mul(self, o: Int) -> Int { Int(self.0 * o.0) } }
+/// # impl Div for Int { type Output = Int; fn div(self, o: Int) -> Int { Int(self.0 / o.0) } }
+/// # impl Rem for Int { type Output = Int; fn rem(self, o: Int) -> Int { Int(self.0 % o.0) } }
+/// # impl num_traits::Zero for Int {
+/// #     fn zero() -> Int { Int(0) }
+/// #     fn is_zero(&self) -> bool { self.0 == 0 }
+/// # }
+/// # impl num_traits::One for Int { fn one() -> Int { Int(1) } }
+/// # impl num_traits::Num for Int {
+/// #     type FromStrRadixErr = std::num::ParseIntError;
+/// #     fn from_str_radix(s: &str, radix: u32) -> Result<Int, Self::FromStrRadixErr> {
+/// #         i64::from_str_radix(s, radix).map(Int)
+/// #     }
+/// # }
+///
+/// impl Determinant for Int {}
+///
+/// let matrix = Matrix::from_vec(2, vec![Int(1), Int(2), Int(3), Int(4)]);
+/// assert_eq!(matrix.determinant(), Some(Int(-2)));
+/// ```
+pub trait Determinant: Num + Copy + Element {
+    /// Calculate the determinant of the `n * n` matrix stored row by row in `data`, returning
+    /// `None` if the element type cannot represent it. The contents of `data` are used as scratch
+    /// space. The provided method cannot detect overflow, so it always succeeds.
+    fn determinant(data: &mut [Self], n: usize) -> Option<Self> {
+        bareiss(
+            data,
+            n,
+            |a, b| Some(a * b),
+            |a, b| Some(a - b),
+            |a, b| Some(a / b),
+        )
+    }
+
+    /// Calculate the determinant of the `n * n` matrix stored row by row in `data`,
+    /// returning `None` if an intermediate value overflows (or, for floats, if the result is not
+    /// finite). The contents of `data` are used as scratch space. The provided method cannot detect
+    /// overflow, so it always succeeds.
+    fn checked_determinant(data: &mut [Self], n: usize) -> Option<Self> {
+        Self::determinant(data, n)
+    }
+}
+
+// Fraction-free elimination. Each arithmetic operation goes through the given closures so the
+// same code serves both the plain and the overflow checked determinant.
+fn bareiss<T, M, S, D>(data: &mut [T], n: usize, mul: M, sub: S, div: D) -> Option<T>
+where
+    T: Num + Copy + Element,
+    M: Fn(T, T) -> Option<T> + Element,
+    S: Fn(T, T) -> Option<T> + Element,
+    D: Fn(T, T) -> Option<T> + Element,
+{
+    let mut negate = false;
+    let mut prev = T::one();
+
+    for k in 0..n {
+        if data[k * n + k].is_zero() {
+            match ((k + 1)..n).find(|&row| !data[row * n + k].is_zero()) {
+                Some(row) => {
+                    for j in 0..n {
+                        data.swap(row * n + j, k * n + j);
+                    }
+                    negate = !negate;
+                }
+                None => return Some(T::zero()),
+            }
+        }
+
+        let pivot = data[k * n + k];
+        let (top, rest) = data.split_at_mut((k + 1) * n);
+        let pivot_row = &top[k * n..];
+        parallel::try_for_each_row(rest, n, |row| {
+            let lead = row[k];
+            for (entry, &above) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                *entry = div(sub(mul(*entry, pivot)?, mul(lead, above)?)?, prev)?;
+            }
+            Some(())
+        })?;
+
+        prev = pivot;
+    }
+
+    if negate {
+        sub(T::zero(), prev)
+    } else {
+        Some(prev)
+    }
+}
+
+// Gaussian elimination, swapping the largest remaining entry of each column (as measured by
+// `magnitude`) into the pivot
+fn pivoted<T, R, M>(data: &mut [T], n: usize, magnitude: M) -> T
+where
+    T: Num + Copy + Neg<Output = T> + Element,
+    R: PartialOrd,
+    M: Fn(T) -> R,
+{
+    let mut det = T::one();
+
+    for k in 0..n {
+        let mut pivot = k;
+        for row in (k + 1)..n {
+            if magnitude(data[row * n + k]) > magnitude(data[pivot * n + k]) {
+                pivot = row;
+            }
+        }
+
+        if data[pivot * n + k].is_zero() {
+            return T::zero();
+        }
+
+        if pivot != k {
+            for j in 0..n {
+                data.swap(pivot * n + j, k * n + j);
+            }
+            det = -det;
+        }
+
+        let diag = data[k * n + k];
+        det = det * diag;
+
+        let (top, rest) = data.split_at_mut((k + 1) * n);
+        let pivot_row = &top[k * n..];
+        parallel::for_each_row(rest, n, |row| {
+            let factor = row[k] / diag;
+            for (entry, &above) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                *entry = *entry - factor * above;
+            }
+        });
+    }
+
+    det
+}
+
+macro_rules! impl_signed {
+    ($($t:ty)*) => {$(
+        impl Determinant for $t {
+            fn checked_determinant(data: &mut [Self], n: usize) -> Option<Self> {
+                bareiss(data, n, <$t>::checked_mul, <$t>::checked_sub, <$t>::checked_div)
+            }
+        }
+    )*};
+}
+
+// Unsigned elimination overflows on every negative intermediate value, not just on large ones, so
+// both methods are checked
+macro_rules! impl_unsigned {
+    ($($t:ty)*) => {$(
+        impl Determinant for $t {
+            fn determinant(data: &mut [Self], n: usize) -> Option<Self> {
+                bareiss(data, n, <$t>::checked_mul, <$t>::checked_sub, <$t>::checked_div)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float {
+    ($($t:ty)*) => {$(
+        impl Determinant for $t {
+            fn determinant(data: &mut [Self], n: usize) -> Option<Self> {
+                Some(pivoted(data, n, <$t>::abs))
+            }
+
+            fn checked_determinant(data: &mut [Self], n: usize) -> Option<Self> {
+                Self::determinant(data, n).filter(|det| det.is_finite())
+            }
+        }
+    )*};
+}
+
+impl_signed!(i8 i16 i32 i64 i128 isize);
+impl_unsigned!(u8 u16 u32 u64 u128 usize);
+impl_float!(f32 f64);
+
+#[cfg(feature = "complex")]
+impl<T: Float + Element> Determinant for Complex<T> {
+    fn determinant(data: &mut [Self], n: usize) -> Option<Self> {
+        Some(pivoted(data, n, |z: Self| z.norm_sqr()))
+    }
+
+    fn checked_determinant(data: &mut [Self], n: usize) -> Option<Self> {
+        Self::determinant(data, n).filter(|det| det.re.is_finite() && det.im.is_finite())
+    }
+}
+
+#[cfg(test)]
+mod determinant_tests {
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_exact_integers() {
+        // Vandermonde matrix of 2..=7, whose determinant is the product of all differences
+        let vandermonde: Matrix<i32> = Matrix::from_vec(
+            6,
+            (2..8)
+                .flat_map(|x: i32| (0..6).map(move |j| x.pow(j)))
+                .collect(),
+        );
+
+        assert_eq!(vandermonde.determinant(), Some(34560));
+        assert_eq!(vandermonde.checked_determinant(), Ok(34560));
+    }
+
+    #[test]
+    fn test_zero_pivot() {
+        let matrix = Matrix::from_vec(3, vec![0, 2, 1, 3, 0, 1, 1, 1, 0]);
+
+        assert_eq!(matrix.determinant(), Some(5));
+        assert_eq!(Matrix::<i64>::zeros(3).determinant(), Some(0));
+        assert_eq!(Matrix::<i64>::zeros(0).determinant(), Some(1));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let small = Matrix::from_vec(2, vec![100_000i32, 1, 1, 100_000]);
+        let large = Matrix::from_vec(2, vec![100_000i64, 1, 1, 100_000]);
+
+        assert_eq!(small.checked_determinant(), Err(MatrixError::Overflow));
+        assert_eq!(large.checked_determinant(), Ok(9_999_999_999));
+        assert_eq!(
+            Matrix::<u8>::identity(2).checked_determinant(),
+            Ok(1),
+            "unsigned determinants work while they stay positive"
+        );
+
+        // `det [[1, 2], [3, 4]] = -2`
+        let unsigned = Matrix::from_vec(2, vec![1u32, 2, 3, 4]);
+        assert_eq!(unsigned.determinant(), None);
+        assert_eq!(unsigned.try_determinant(), Err(MatrixError::Overflow));
+        assert_eq!(unsigned.checked_determinant(), Err(MatrixError::Overflow));
+        assert_eq!(Matrix::from_vec(2, vec![4u32, 3, 2, 1]).determinant(), None);
+        assert_eq!(
+            Matrix::from_vec(2, vec![3u32, 2, 1, 4]).determinant(),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn test_floats() {
+        let matrix: Matrix<f64> =
+            Matrix::from_vec(3, vec![1e-20, 1.0, 0.0, 1.0, 1.0, 0.0, 0.0, 0.0, 2.0]);
+        let det = matrix.determinant().unwrap();
+
+        assert!((det + 2.0).abs() < 1e-12);
+        assert_eq!(
+            Matrix::from_vec(1, vec![f64::MAX])
+                .scalar_mul(2.0)
+                .checked_determinant(),
+            Err(MatrixError::Overflow)
+        );
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex() {
+        use num_complex::Complex;
+
+        // `det [[1 + i, 2], [i, 1 - i]] = (1 + i)(1 - i) - 2i = 2 - 2i`
+        let matrix = Matrix::from_vec(
+            2,
+            vec![
+                Complex::new(1.0, 1.0),
+                Complex::new(2.0, 0.0),
+                Complex::new(0.0, 1.0),
+                Complex::new(1.0, -1.0),
+            ],
+        );
+        let det = matrix.determinant().unwrap();
+
+        assert!((det - Complex::new(2.0, -2.0)).norm() < 1e-12);
+        assert_eq!(
+            Matrix::<Complex<f64>>::zeros(2).checked_determinant(),
+            Ok(Complex::new(0.0, 0.0))
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// The error type returned by the fallible operations on a `Matrix`
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::MatrixError;
+///
+/// let matrix = matrix![f64; (2, 3); 1, 2, 3; 4, 5, 6];
+/// assert_eq!(matrix.inverse(), Err(MatrixError::NotSquare));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum MatrixError {
+    /// The dimensions (`(height, width)`) of the two operands are incompatible
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+
+    /// The location (`(row, col)`) lies outside of a matrix with the given size
+    OutOfBounds {
+        index: (usize, usize),
+        size: (usize, usize),
+    },
+
+    /// The operation is only defined for square matrices
+    NotSquare,
+
+    /// The matrix is singular, or (for floats) too close to singular to work with reliably. For
+    /// integers, this also covers matrices whose inverse is not an integer matrix.
+    Singular,
+
+    /// The matrix is not positive definite, which was detected at the given pivot
+    NotPositiveDefinite { pivot: usize },
+
+    /// The operator is not positive definite, which an iterative solver detected as a search
+    /// direction with non-positive curvature at the given iteration
+    NonPositiveCurvature { iteration: usize },
+
+    /// An intermediate value overflowed the element type
+    Overflow,
+
+    /// The number of elements given does not match the requested dimensions
+    InvalidLength { expected: usize, found: usize },
+
+    /// An iterative algorithm did not converge within its iteration limit
+    NotConverged,
+
+    /// The index arrays of a compressed sparse matrix are unsorted, repeat an index, or do not
+    /// describe the given number of rows or columns
+    InvalidStructure,
+
+    /// The operator does not support the operation, such as a preconditioner built from the
+    /// entries of a `LinearOperator` that does not expose them
+    Unsupported,
+}
+
+impl Display for MatrixError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { left, right } => write!(
+                fmt,
+                "dimension mismatch between a {}x{} and a {}x{} matrix",
+                left.0, left.1, right.0, right.1
+            ),
+            MatrixError::OutOfBounds { index, size } => write!(
+                fmt,
+                "index ({}, {}) is out of bounds for a {}x{} matrix",
+                index.0, index.1, size.0, size.1
+            ),
+            MatrixError::NotSquare => write!(fmt, "the matrix is not square"),
+            MatrixError::Singular => write!(fmt, "the matrix is singular"),
+            MatrixError::NotPositiveDefinite { pivot } => write!(
+                fmt,
+                "the matrix is not positive definite (failed at pivot {})",
+                pivot
+            ),
+            MatrixError::NonPositiveCurvature { iteration } => write!(
+                fmt,
+                "the operator is not positive definite (non-positive curvature at iteration {})",
+                iteration
+            ),
+            MatrixError::Overflow => write!(fmt, "arithmetic overflow"),
+            MatrixError::InvalidLength { expected, found } => {
+                write!(fmt, "expected {} elements, found {}", expected, found)
+            }
+            MatrixError::NotConverged => write!(fmt, "the iteration did not converge"),
+            MatrixError::InvalidStructure => write!(fmt, "the sparse matrix structure is invalid"),
+            MatrixError::Unsupported => write!(fmt, "the operation is not supported"),
+        }
+    }
+}
+
+impl Error for MatrixError {}
@@ -0,0 +1,333 @@
+use std::convert::TryInto;
+
+use crate::parallel::Element;
+use crate::{Matrix, MatrixError, MatrixView, MatrixViewMut};
+
+use num_traits::Num;
+
+// The block of `c` whose partial sums are held in registers at once
+const MR: usize = 4;
+const NR: usize = 4;
+
+// The rows of `a`, shared depth and columns of `b` covered by one cache tile. The packed `KC * NC`
+// block of `b` is reused by every row block of `a`, and each packed `MC * KC` block of `a` by every
+// column panel of `b`, while they are still in cache.
+const MC: usize = 64;
+const KC: usize = 256;
+const NC: usize = 256;
+
+// The rows of a product computed at once when it replaces one of its operands, enough for the
+// `rayon` feature to split them between threads
+pub(crate) const BAND_ROWS: usize = 4 * MC;
+
+/// General matrix multiplication in the style of BLAS, computing `c = alpha * a * b + beta * c` in
+/// place. The only allocations are two packing buffers, each holding at most one cache tile of an
+/// operand, whatever the size of the matrices. A zero `beta` overwrites `c` rather than scaling
+/// it, so its previous entries (even `NaN`s) do not affect the result.
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::gemm;
+///
+/// let a = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+/// let b = matrix![(3, 2); 1, 0; 0, 1; 1, 1];
+/// let mut c = matrix![(2, 2); 1, 1; 1, 1];
+///
+/// gemm(2, &a, &b, 10, &mut c).unwrap();
+/// assert_eq!(c, matrix![(2, 2); 18, 20; 30, 32]);
+/// ```
+/// # Failure
+/// Fails with `MatrixError::DimensionMismatch` if the width of `a` is not the height of `b`, or if
+/// `c` is not the size of their product
+pub fn gemm<T: Num + Clone + Copy + Element>(
+    alpha: T,
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    beta: T,
+    c: &mut Matrix<T>,
+) -> Result<(), MatrixError> {
+    if a.width != b.height {
+        return Err(MatrixError::DimensionMismatch {
+            left: a.size(),
+            right: b.size(),
+        });
+    }
+
+    if c.size() != (a.height, b.width) {
+        return Err(MatrixError::DimensionMismatch {
+            left: (a.height, b.width),
+            right: c.size(),
+        });
+    }
+
+    kernel(
+        alpha,
+        &a.as_view(),
+        &b.as_view(),
+        beta,
+        &mut c.as_view_mut(),
+    );
+    Ok(())
+}
+
+// Computes `c = alpha * a * b + beta * c` for views of compatible sizes. With the `rayon` feature,
+// large products are split into bands of whole cache tiles of rows, which are computed on separate
+// threads exactly as they would be serially.
+pub(crate) fn kernel<T: Num + Clone + Copy + Element>(
+    alpha: T,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    beta: T,
+    c: &mut MatrixViewMut<T>,
+) {
+    #[cfg(feature = "rayon")]
+    {
+        let ((height, depth), width) = (a.size(), b.size().1);
+        if height > MC && height * depth * width >= crate::parallel::MIN_PARALLEL_WORK {
+            let middle = (height / MC / 2).max(1) * MC;
+            if let Some((mut top, mut bottom)) = c.split_rows_mut(middle) {
+                let (a_top, a_bottom) = (a.view(..middle, ..), a.view(middle.., ..));
+                rayon::join(
+                    || kernel(alpha, &a_top, b, beta, &mut top),
+                    || kernel(alpha, &a_bottom, b, beta, &mut bottom),
+                );
+                return;
+            }
+        }
+    }
+
+    serial_kernel(alpha, a, b, beta, c);
+}
+
+// Computes `c = alpha * a * b + beta * c`, walking `c` in cache tiles and each tile in register
+// blocks. Every `KC * NC` block of `b` and `MC * KC` block of `a` is first packed into a contiguous
+// buffer, in the order the micro-kernel reads it, so the innermost loops only walk plain slices.
+fn serial_kernel<T: Num + Clone + Copy>(
+    alpha: T,
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    beta: T,
+    c: &mut MatrixViewMut<T>,
+) {
+    let ((height, depth), width) = (a.size(), b.size().1);
+    debug_assert_eq!(b.size().0, depth);
+    debug_assert_eq!(c.size(), (height, width));
+
+    for row in 0..height {
+        for col in 0..width {
+            let entry = c.at_mut(row, col);
+            *entry = if beta.is_zero() {
+                T::zero()
+            } else {
+                beta * *entry
+            };
+        }
+    }
+
+    if alpha.is_zero() || depth == 0 {
+        return;
+    }
+
+    let mut packed_a = vec![T::zero(); MC.min(height).next_multiple_of(MR) * KC.min(depth)];
+    let mut packed_b = vec![T::zero(); NC.min(width).next_multiple_of(NR) * KC.min(depth)];
+
+    for jc in (0..width).step_by(NC) {
+        let nc = NC.min(width - jc);
+        for pc in (0..depth).step_by(KC) {
+            let kc = KC.min(depth - pc);
+            pack_b(b, &mut packed_b, (pc, jc), (kc, nc));
+
+            for ic in (0..height).step_by(MC) {
+                let mc = MC.min(height - ic);
+                pack_a(a, &mut packed_a, (ic, pc), (mc, kc));
+
+                let panels_b = packed_b.chunks_exact(kc * NR).take(nc.div_ceil(NR));
+                for (jr, panel_b) in (jc..jc + nc).step_by(NR).zip(panels_b) {
+                    let panels_a = packed_a.chunks_exact(kc * MR).take(mc.div_ceil(MR));
+                    for (ir, panel_a) in (ic..ic + mc).step_by(MR).zip(panels_a) {
+                        let sums = micro_kernel(panel_a, panel_b);
+                        let size = (MR.min(ic + mc - ir), NR.min(jc + nc - jr));
+                        write_block(alpha, &sums, c, (ir, jr), size);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Packs the `height * depth` block of `a` at `(row, k)` into panels of `MR` rows, each stored
+// column by column. Rows past the edge of `a` are padded with zeros, so every panel is full.
+fn pack_a<T: Num + Clone + Copy>(
+    a: &MatrixView<T>,
+    packed: &mut [T],
+    (row, k): (usize, usize),
+    (height, depth): (usize, usize),
+) {
+    let panels = packed.chunks_exact_mut(depth * MR);
+    for (panel, offset) in panels.zip((0..height).step_by(MR)) {
+        let rows = MR.min(height - offset);
+        for (col, entries) in panel.chunks_exact_mut(MR).enumerate() {
+            for (i, entry) in entries.iter_mut().enumerate() {
+                *entry = if i < rows {
+                    a.at(row + offset + i, k + col)
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+// Packs the `depth * width` block of `b` at `(k, col)` into panels of `NR` columns, each stored row
+// by row. Columns past the edge of `b` are padded with zeros, so every panel is full.
+fn pack_b<T: Num + Clone + Copy>(
+    b: &MatrixView<T>,
+    packed: &mut [T],
+    (k, col): (usize, usize),
+    (depth, width): (usize, usize),
+) {
+    let panels = packed.chunks_exact_mut(depth * NR);
+    for (panel, offset) in panels.zip((0..width).step_by(NR)) {
+        let cols = NR.min(width - offset);
+        for (row, entries) in panel.chunks_exact_mut(NR).enumerate() {
+            for (j, entry) in entries.iter_mut().enumerate() {
+                *entry = if j < cols {
+                    b.at(k + row, col + offset + j)
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+// Multiplies a packed panel of `MR` rows of `a` by a packed panel of `NR` columns of `b` over the
+// same depth. The partial sums live in a fixed size array so that they can be kept in registers.
+fn micro_kernel<T: Num + Clone + Copy>(panel_a: &[T], panel_b: &[T]) -> [[T; NR]; MR] {
+    let mut sums = [[T::zero(); NR]; MR];
+
+    for (left, right) in panel_a.chunks_exact(MR).zip(panel_b.chunks_exact(NR)) {
+        let left: &[T; MR] = left.try_into().unwrap();
+        let right: &[T; NR] = right.try_into().unwrap();
+        for (row_sums, &left) in sums.iter_mut().zip(left) {
+            for (sum, &right) in row_sums.iter_mut().zip(right) {
+                *sum = *sum + left * right;
+            }
+        }
+    }
+
+    sums
+}
+
+// Adds `alpha` times the partial sums to the block of at most `MR * NR` entries of `c` at
+// `(row, col)`, dropping the sums of the padding
+fn write_block<T: Num + Clone + Copy>(
+    alpha: T,
+    sums: &[[T; NR]; MR],
+    c: &mut MatrixViewMut<T>,
+    (row, col): (usize, usize),
+    (height, width): (usize, usize),
+) {
+    for (row_offset, row_sums) in sums.iter().take(height).enumerate() {
+        for (col_offset, &sum) in row_sums.iter().take(width).enumerate() {
+            let entry = c.at_mut(row + row_offset, col + col_offset);
+            *entry = *entry + alpha * sum;
+        }
+    }
+}
+
+#[cfg(test)]
+mod gemm_tests {
+    use super::gemm;
+    use crate::test_util::sample;
+    use crate::{Matrix, MatrixError};
+
+    // The textbook triple loop, to check the tiling against
+    fn naive(a: &Matrix<i64>, b: &Matrix<i64>) -> Matrix<i64> {
+        let (height, depth, width) = (a.size().0, a.size().1, b.size().1);
+        let mut body = Vec::with_capacity(height * width);
+        for row in 0..height {
+            for col in 0..width {
+                body.push((0..depth).map(|k| a[(row, k)] * b[(k, col)]).sum());
+            }
+        }
+
+        Matrix::from_vec((height, width), body)
+    }
+
+    #[test]
+    fn test_tiling() {
+        // Sizes that leave partial register blocks and span several cache tiles of every kind
+        for &(height, depth, width) in &[(1, 1, 1), (5, 3, 7), (67, 300, 5), (9, 257, 261)] {
+            let a = sample((height, depth), 1);
+            let b = sample((depth, width), 2);
+
+            assert_eq!(&a * &b, naive(&a, &b));
+            assert_eq!(a.as_view().try_mul(&b.as_view()), Ok(naive(&a, &b)));
+        }
+
+        let a = sample((6, 5), 3);
+        let b = sample((6, 5), 4);
+        assert_eq!(a.transpose_view() * b.as_view(), {
+            let mut a_t = a.clone();
+            a_t.transpose();
+            naive(&a_t, &b)
+        });
+    }
+
+    #[test]
+    fn test_scaling() {
+        let a = sample((3, 4), 5);
+        let b = sample((4, 2), 6);
+        let c = sample((3, 2), 7);
+
+        let mut result = c.clone();
+        gemm(3, &a, &b, -2, &mut result).unwrap();
+        assert_eq!(result, naive(&a, &b) * 3 - c.clone() * 2);
+
+        let mut result = c.clone();
+        gemm(0, &a, &b, 1, &mut result).unwrap();
+        assert_eq!(result, c);
+
+        let mut nan = Matrix::from_vec((1, 1), vec![f64::NAN]);
+        gemm(
+            1.0,
+            &Matrix::identity(1),
+            &Matrix::identity(1),
+            0.0,
+            &mut nan,
+        )
+        .unwrap();
+        assert_eq!(nan, Matrix::identity(1));
+
+        let mut empty = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+        gemm(
+            1,
+            &Matrix::zeros((2, 0)),
+            &Matrix::zeros((0, 2)),
+            1,
+            &mut empty,
+        )
+        .unwrap();
+        assert_eq!(empty, Matrix::from_vec(2, vec![1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let mut c = Matrix::<i32>::zeros(2);
+
+        assert_eq!(
+            gemm(1, &Matrix::zeros((2, 3)), &Matrix::zeros(2), 0, &mut c),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 2)
+            })
+        );
+        assert_eq!(
+            gemm(1, &Matrix::zeros((2, 3)), &Matrix::zeros((3, 1)), 0, &mut c),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 1),
+                right: (2, 2)
+            })
+        );
+    }
+}
@@ -1,21 +1,53 @@
-use std::convert::From;
+use std::convert::{From, TryFrom};
 use std::fmt::{self, Display, Formatter};
+use std::iter::FromIterator;
 use std::ops::Index;
+use std::{slice, vec};
 
 use crate::size::Size;
-use crate::Matrix;
+use crate::{Layout, Matrix, MatrixError};
 
 use num_traits::Num;
 
-/// Pretty print of the `Matrix` via this impl
+/// Pretty print of the `Matrix` via this impl. Each column is right aligned to its widest entry,
+/// and a precision (`{:.2}`) or minimum width (`{:6}`) is passed through to every entry.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let matrix = matrix![f64; (2, 2); 1, -2.5; 10, 4];
+///
+/// assert_eq!(format!("{}", matrix), " 1 -2.5 \n10    4 \n");
+/// assert_eq!(format!("{:.1}", matrix), " 1.0 -2.5 \n10.0  4.0 \n");
+/// ```
 impl<T> Display for Matrix<T>
 where
     T: Num + Clone + Copy + Display,
 {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
-        for row in 1..=self.height {
-            for index in ((row - 1) * self.height)..(self.width * row) {
-                write!(fmt, "{} ", self.data[index])?;
+        // Format every entry up front, so that each column can be padded to its widest entry
+        let cells: Vec<String> = self
+            .data
+            .iter()
+            .map(|x| match fmt.precision() {
+                Some(precision) => format!("{:.*}", precision, x),
+                None => format!("{}", x),
+            })
+            .collect();
+
+        let mut widths = vec![fmt.width().unwrap_or(0); self.width];
+        for (index, cell) in cells.iter().enumerate() {
+            let col = index % self.width;
+            widths[col] = widths[col].max(cell.chars().count());
+        }
+
+        for row in 0..self.height {
+            for (col, width) in widths.iter().enumerate() {
+                write!(
+                    fmt,
+                    "{:>width$} ",
+                    cells[row * self.width + col],
+                    width = width
+                )?;
             }
 
             writeln!(fmt)?;
@@ -26,12 +58,22 @@ where
 }
 
 /// Allows for the indexing of `Matrix`
-impl<T: Num, S: Size> Index<S> for Matrix<T> {
+impl<T: Num + Clone + Copy, L: Layout, S: Size> Index<S> for Matrix<T, L> {
     type Output = T;
 
     fn index(&self, pos: S) -> &Self::Output {
         let (h, w) = pos.dim();
-        &self.data[h * self.width + w]
+        if h >= self.height || w >= self.width {
+            panic!(
+                "{}",
+                MatrixError::OutOfBounds {
+                    index: (h, w),
+                    size: (self.height, self.width),
+                }
+            );
+        }
+
+        &self.data[self.offset(h, w)]
     }
 }
 
@@ -41,3 +83,82 @@ impl<T: Num + Clone + Copy> From<Matrix<T>> for Vec<T> {
         mat.as_slice().to_vec()
     }
 }
+
+/// Consumes the `Matrix`, yielding its entries in row-major order
+impl<T: Num + Clone + Copy> IntoIterator for Matrix<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+/// Iterates over the entries of a borrowed `Matrix` in row-major order, the same as `Matrix::iter`
+impl<'a, T: Num + Clone + Copy> IntoIterator for &'a Matrix<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates mutably over the entries of a borrowed `Matrix` in row-major order, the same as
+/// `Matrix::iter_mut`
+impl<'a, T: Num + Clone + Copy> IntoIterator for &'a mut Matrix<T> {
+    type Item = &'a mut T;
+    type IntoIter = slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// Collects an iterator into a column vector. Use `Matrix::from_iter_shape` for any other shape.
+/// ```
+/// use mtrs::Matrix;
+///
+/// let matrix: Matrix<i32> = (1..4).collect();
+/// assert_eq!(matrix, Matrix::from_vec((3, 1), vec![1, 2, 3]));
+/// ```
+impl<T: Num + Clone + Copy> FromIterator<T> for Matrix<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let body: Vec<T> = iter.into_iter().collect();
+        Self::from_vec((body.len(), 1), body)
+    }
+}
+
+/// Implements the `TryFrom<Vec<Vec<T>>>` trait for `Matrix<T>`, where each inner `Vec` is a row
+/// ```
+/// use std::convert::TryFrom;
+/// use mtrs::{Matrix, MatrixError};
+///
+/// let matrix = Matrix::try_from(vec![vec![1, 2], vec![3, 4]]);
+/// assert_eq!(matrix, Ok(Matrix::from_vec(2, vec![1, 2, 3, 4])));
+///
+/// let ragged = Matrix::try_from(vec![vec![1, 2], vec![3]]);
+/// assert_eq!(ragged, Err(MatrixError::InvalidLength { expected: 2, found: 1 }));
+/// ```
+impl<T: Num + Clone + Copy> TryFrom<Vec<Vec<T>>> for Matrix<T> {
+    type Error = MatrixError;
+
+    fn try_from(rows: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut body = Vec::with_capacity(height * width);
+
+        for row in rows {
+            if row.len() != width {
+                return Err(MatrixError::InvalidLength {
+                    expected: width,
+                    found: row.len(),
+                });
+            }
+
+            body.extend(row);
+        }
+
+        Ok(Self::from_vec((height, width), body))
+    }
+}
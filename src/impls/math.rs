@@ -1,116 +1,180 @@
-use std::iter::Sum;
 use std::ops::*;
 
+use crate::parallel::{self, Element};
 use crate::Matrix;
 
 use num_traits::Num;
 
-/// Implements addition between `Matrix<T>` and `Matrix<T>`
-impl<T: Num + Clone + Copy> Add for Matrix<T> {
-    type Output = Self;
+// Implements an elementwise operator between matrices for every combination of owned and
+// borrowed operands, along with its compound assignment. An owned left operand is updated in place
+// and returned, so only operations between two borrowed matrices allocate a new one.
+macro_rules! impl_matrix_op {
+    ($($op:ident $method:ident $checked:ident, $assign:ident $assign_method:ident $try_assign:ident, $doc:expr;)*) => {$(
+        #[doc = $doc]
+        impl<T: Num + Clone + Copy + Element> $op for Matrix<T> {
+            type Output = Self;
 
-    fn add(self, other: Self) -> Self {
-        if self.size() != other.size() {
-            unimplemented!();
-        } else {
-            let mut new_body = Vec::new();
-            let other_slice = other.as_slice();
+            fn $method(mut self, other: Self) -> Self {
+                self.$assign_method(&other);
+                self
+            }
+        }
 
-            for (index, i) in self.data.iter().enumerate() {
-                new_body.push(*i + other_slice[index]);
+        #[doc = $doc]
+        impl<'a, T: Num + Clone + Copy + Element> $op<&'a Matrix<T>> for Matrix<T> {
+            type Output = Self;
+
+            fn $method(mut self, other: &'a Matrix<T>) -> Self {
+                self.$assign_method(other);
+                self
             }
+        }
 
-            Self::from_vec(self.size(), new_body)
+        #[doc = $doc]
+        impl<'a, T: Num + Clone + Copy + Element> $op<Matrix<T>> for &'a Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: Matrix<T>) -> Matrix<T> {
+                self.$method(&other)
+            }
         }
-    }
-}
 
-/// Implements subtraction between `Matrix<T>` and `Matrix<T>`
-impl<T: Num + Clone + Copy> Sub for Matrix<T> {
-    type Output = Self;
+        #[doc = $doc]
+        impl<'a, 'b, T: Num + Clone + Copy + Element> $op<&'b Matrix<T>> for &'a Matrix<T> {
+            type Output = Matrix<T>;
 
-    fn sub(self, other: Self) -> Self::Output {
-        if self.size() != other.size() {
-            unimplemented!();
-        } else {
-            let mut new_body = Vec::new();
-            let other_slice = other.as_slice();
+            fn $method(self, other: &'b Matrix<T>) -> Matrix<T> {
+                self.$checked(other).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
 
-            for (index, i) in self.data.iter().enumerate() {
-                new_body.push(*i - other_slice[index]);
+        #[doc = $doc]
+        impl<T: Num + Clone + Copy + Element> $assign for Matrix<T> {
+            fn $assign_method(&mut self, other: Self) {
+                self.$assign_method(&other)
             }
+        }
 
-            Self::from_vec(self.size(), new_body)
+        #[doc = $doc]
+        impl<'a, T: Num + Clone + Copy + Element> $assign<&'a Matrix<T>> for Matrix<T> {
+            fn $assign_method(&mut self, other: &'a Matrix<T>) {
+                self.$try_assign(other).unwrap_or_else(|err| panic!("{}", err))
+            }
         }
-    }
+    )*};
 }
 
-/// Implements multiplication between `Matrix<T>` and `Matrix<T>`
-impl<T: Num + Clone + Copy + Sum> Mul for Matrix<T> {
-    type Output = Self;
-
-    fn mul(self, other: Self) -> Self {
-        if self.width != other.height {
-            panic!("Incorrect bounds for the two Matrices");
-        }
+impl_matrix_op! {
+    Add add try_add, AddAssign add_assign try_add_assign,
+        "Implements addition between matrices\n# Panics\nPanics if the sizes differ, see \
+        `Matrix::try_add` and `Matrix::try_add_assign` for non-panicking versions";
+    Sub sub try_sub, SubAssign sub_assign try_sub_assign,
+        "Implements subtraction between matrices\n# Panics\nPanics if the sizes differ, see \
+        `Matrix::try_sub` and `Matrix::try_sub_assign` for non-panicking versions";
+}
 
-        let mut body: Vec<Vec<T>> = vec![Vec::new(); self.height];
+// Implements multiplication between matrices for every combination of owned and borrowed
+// operands. A product cannot be formed in the storage of an operand, so they all go through
+// `Matrix::try_mul` and its kernel.
+macro_rules! impl_matrix_mul {
+    ($(($left:ty, $right:ty, $($lt:lifetime),*);)*) => {$(
+        /// Implements multiplication between matrices
+        /// # Panics
+        /// Panics if the inner dimensions differ, see `Matrix::try_mul` and
+        /// `Matrix::try_mul_assign` for non-panicking versions
+        impl<$($lt,)* T: Num + Clone + Copy + Element> Mul<$right> for $left {
+            type Output = Matrix<T>;
 
-        for (i, row) in self.as_vec().iter().enumerate() {
-            for col in other.cols() {
-                body[i].push(
-                    row.iter()
-                        .zip(col.iter())
-                        .map(|(&left, &right)| left * right)
-                        .sum::<T>(),
-                );
+            fn mul(self, other: $right) -> Matrix<T> {
+                self.try_mul(&other).unwrap_or_else(|err| panic!("{}", err))
             }
         }
+    )*};
+}
 
-        Self::from_vec(
-            (body.len(), body[0].len()),
-            body.iter().flat_map(|row| row.iter().copied()).collect(),
-        )
-    }
+impl_matrix_mul! {
+    (Matrix<T>, Matrix<T>,);
+    (Matrix<T>, &'a Matrix<T>, 'a);
+    (&'a Matrix<T>, Matrix<T>, 'a);
+    (&'a Matrix<T>, &'b Matrix<T>, 'a, 'b);
 }
 
-/// Implements addition between `Matrix<T>` and `T`
-impl<T: Num + Clone + Copy> Add<T> for Matrix<T> {
-    type Output = Self;
+/// Implements multiplication between matrices in place, see `Matrix::try_mul_assign`
+/// # Panics
+/// Panics if the inner dimensions differ
+impl<T: Num + Clone + Copy + Element> MulAssign for Matrix<T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self *= &other
+    }
+}
 
-    #[inline]
-    fn add(self, rhs: T) -> Self::Output {
-        self.scalar_add(rhs)
+/// Implements multiplication between matrices in place, see `Matrix::try_mul_assign`
+/// # Panics
+/// Panics if the inner dimensions differ
+impl<'a, T: Num + Clone + Copy + Element> MulAssign<&'a Matrix<T>> for Matrix<T> {
+    fn mul_assign(&mut self, other: &'a Matrix<T>) {
+        self.try_mul_assign(other)
+            .unwrap_or_else(|err| panic!("{}", err))
     }
 }
 
-/// Implements subtraction between `Matrix<T>` and `T`
-impl<T: Num + Clone + Copy> Sub<T> for Matrix<T> {
-    type Output = Self;
+// Implements an operator between a matrix and a scalar, applied to every entry. The owned matrix
+// and the compound assignment reuse the existing storage.
+macro_rules! impl_scalar_op {
+    ($($op:ident $method:ident $scalar:ident, $assign:ident $assign_method:ident, $doc:expr;)*) => {$(
+        #[doc = $doc]
+        impl<T: Num + Clone + Copy + Element> $op<T> for Matrix<T> {
+            type Output = Self;
 
-    #[inline]
-    fn sub(self, rhs: T) -> Self::Output {
-        self.scalar_sub(rhs)
-    }
+            #[inline]
+            fn $method(mut self, rhs: T) -> Self::Output {
+                self.$assign_method(rhs);
+                self
+            }
+        }
+
+        #[doc = $doc]
+        impl<'a, T: Num + Clone + Copy + Element> $op<T> for &'a Matrix<T> {
+            type Output = Matrix<T>;
+
+            #[inline]
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$scalar(rhs)
+            }
+        }
+
+        #[doc = $doc]
+        impl<T: Num + Clone + Copy + Element> $assign<T> for Matrix<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                parallel::for_each_mut(self.as_mut_slice(), |entry| *entry = $op::$method(*entry, rhs));
+            }
+        }
+    )*};
 }
 
-/// Implements multiplication between `Matrix<T>` and `T`
-impl<T: Num + Clone + Copy> Mul<T> for Matrix<T> {
+impl_scalar_op! {
+    Add add scalar_add, AddAssign add_assign, "Implements addition between `Matrix<T>` and `T`";
+    Sub sub scalar_sub, SubAssign sub_assign, "Implements subtraction between `Matrix<T>` and `T`";
+    Mul mul scalar_mul, MulAssign mul_assign, "Implements multiplication between `Matrix<T>` and `T`";
+    Div div scalar_div, DivAssign div_assign, "Implements division between `Matrix<T>` and `T`";
+}
+
+/// Negates every entry of the matrix, in place
+impl<T: Num + Clone + Copy + Element + Neg<Output = T>> Neg for Matrix<T> {
     type Output = Self;
 
-    #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
-        self.scalar_mul(rhs)
+    fn neg(mut self) -> Self {
+        parallel::for_each_mut(self.as_mut_slice(), |entry| *entry = -*entry);
+        self
     }
 }
 
-/// Implements division between `Matrix<T>` and `T`
-impl<T: Num + Clone + Copy> Div<T> for Matrix<T> {
-    type Output = Self;
+/// Negates every entry of the borrowed matrix into a new one
+impl<T: Num + Clone + Copy + Element + Neg<Output = T>> Neg for &Matrix<T> {
+    type Output = Matrix<T>;
 
-    #[inline]
-    fn div(self, rhs: T) -> Self::Output {
-        self.scalar_div(rhs)
+    fn neg(self) -> Matrix<T> {
+        -self.clone()
     }
 }
 
@@ -139,4 +203,109 @@ mod math_tests {
             Matrix::from_slice((1, 3), &[2.0, 8.0, 14.0])
         );
     }
+
+    #[test]
+    fn test_reference_ops() {
+        let a = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+        let b = Matrix::from_vec(2, vec![0, 1, 1, 0]);
+
+        assert_eq!(&a + &b, Matrix::from_vec(2, vec![1, 3, 4, 4]));
+        assert_eq!(&a - b.clone(), Matrix::from_vec(2, vec![1, 1, 2, 4]));
+        assert_eq!(a.clone() * &b, Matrix::from_vec(2, vec![2, 1, 4, 3]));
+        assert_eq!(&a * &b, a.clone() * b.clone());
+        assert_eq!(&a * 2 - 1, Matrix::from_vec(2, vec![1, 3, 5, 7]));
+        assert_eq!(-&a, Matrix::from_vec(2, vec![-1, -2, -3, -4]));
+        assert_eq!(-(-a.clone()), a);
+    }
+
+    #[test]
+    fn test_products_share_kernel() {
+        // Float rounding depends on the order of the sums, so every form must use the same kernel
+        let a = Matrix::from_iter_shape((37, 300), (0..11_100).map(|i| (i as f64).sin())).unwrap();
+        let b = Matrix::from_iter_shape((300, 29), (0..8_700).map(|i| (i as f64).cos())).unwrap();
+        let expected = a.try_mul(&b).unwrap();
+
+        assert_eq!(&a * &b, expected);
+        assert_eq!(a.clone() * b.clone(), expected);
+        assert_eq!(a.clone() * &b, expected);
+        assert_eq!(&a * b.clone(), expected);
+
+        let mut assigned = a.clone();
+        assigned *= &b;
+        assert_eq!(assigned, expected);
+    }
+
+    #[test]
+    fn test_assign_ops() {
+        let mut matrix = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+        let identity = Matrix::identity(2);
+
+        matrix += &identity;
+        matrix -= identity.clone() * 3;
+        assert_eq!(matrix, Matrix::from_vec(2, vec![-1, 2, 3, 2]));
+
+        matrix *= 2;
+        matrix += 1;
+        matrix -= 3;
+        matrix /= 2;
+        assert_eq!(matrix, Matrix::from_vec(2, vec![-2, 1, 2, 1]));
+
+        matrix *= &identity;
+        assert_eq!(matrix, Matrix::from_vec(2, vec![-2, 1, 2, 1]));
+    }
+
+    #[test]
+    fn test_mul_assign_reshapes() {
+        let tall = Matrix::from_vec((3, 2), vec![1, 2, 3, 4, 5, 6]);
+        let wide = Matrix::from_vec((2, 3), vec![1, 0, 2, 0, 1, 3]);
+
+        // The product takes the place of the storage, whether it is wider or narrower
+        let mut widened = tall.clone();
+        widened *= &wide;
+        assert_eq!(widened, tall.try_mul(&wide).unwrap());
+        assert_eq!(widened.size(), (3, 3));
+
+        let mut narrowed = widened.clone();
+        narrowed *= tall.clone();
+        assert_eq!(narrowed, widened.try_mul(&tall).unwrap());
+        assert_eq!(narrowed.size(), (3, 2));
+
+        let mut empty = Matrix::<i32>::zeros((2, 0));
+        empty *= Matrix::zeros((0, 3));
+        assert_eq!(empty, Matrix::zeros((2, 3)));
+
+        // Enough rows for several bands, each of which must match the product exactly
+        let tall = Matrix::from_iter_shape((600, 5), (0..3000).map(|i| (i as f64).sin())).unwrap();
+        let wide = Matrix::from_iter_shape((5, 7), (0..35).map(|i| (i as f64).cos())).unwrap();
+        let narrow = Matrix::from_iter_shape((7, 3), (0..21).map(|i| (i as f64).tan())).unwrap();
+
+        let mut assigned = tall.clone();
+        assigned *= &wide;
+        assert_eq!(assigned, tall.try_mul(&wide).unwrap());
+
+        let storage = assigned.as_slice().as_ptr();
+        let expected = assigned.try_mul(&narrow).unwrap();
+        assigned *= &narrow;
+        assert_eq!(assigned, expected);
+        assert_eq!(assigned.as_slice().as_ptr(), storage);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch between a 2x2 and a 3x3 matrix")]
+    fn test_mismatched_sub_assign() {
+        let mut matrix = Matrix::<i32>::zeros(2);
+        matrix -= Matrix::zeros(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch between a 1x3 and a 3x1 matrix")]
+    fn test_mismatched_add() {
+        let _ = Matrix::<i32>::zeros((1, 3)) + Matrix::zeros((3, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch between a 1x3 and a 1x3 matrix")]
+    fn test_mismatched_mul() {
+        let _ = Matrix::<i32>::zeros((1, 3)) * Matrix::zeros((1, 3));
+    }
 }
@@ -0,0 +1,163 @@
+use std::convert::From;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use crate::size::Size;
+use crate::{Element, Matrix, MatrixError, MatrixView, MatrixViewMut};
+
+use num_traits::Num;
+
+// Panics with the message of an out of bounds error
+fn out_of_bounds(index: (usize, usize), size: (usize, usize)) -> ! {
+    panic!("{}", MatrixError::OutOfBounds { index, size })
+}
+
+/// Allows for the indexing of `MatrixView`
+impl<'a, T: Num + Clone + Copy, S: Size> Index<S> for MatrixView<'a, T> {
+    type Output = T;
+
+    fn index(&self, pos: S) -> &Self::Output {
+        let index = pos.dim();
+        self.get(index)
+            .unwrap_or_else(|| out_of_bounds(index, self.size()))
+    }
+}
+
+/// Allows for the indexing of `MatrixViewMut`
+impl<'a, T: Num + Clone + Copy, S: Size> Index<S> for MatrixViewMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, pos: S) -> &Self::Output {
+        let (index, size) = (pos.dim(), self.size());
+        self.get(index)
+            .unwrap_or_else(|| out_of_bounds(index, size))
+    }
+}
+
+/// Allows for writing to a `MatrixViewMut` through indexing
+impl<'a, T: Num + Clone + Copy, S: Size> IndexMut<S> for MatrixViewMut<'a, T> {
+    fn index_mut(&mut self, pos: S) -> &mut Self::Output {
+        let (index, size) = (pos.dim(), self.size());
+        self.get_mut(index)
+            .unwrap_or_else(|| out_of_bounds(index, size))
+    }
+}
+
+/// Views are equal when they have the same size and entries, whatever their strides
+impl<'a, 'b, T: Num + Clone + Copy> PartialEq<MatrixView<'b, T>> for MatrixView<'a, T> {
+    fn eq(&self, other: &MatrixView<'b, T>) -> bool {
+        let (height, width) = self.size();
+        self.size() == other.size()
+            && (0..height).all(|row| (0..width).all(|col| self.at(row, col) == other.at(row, col)))
+    }
+}
+
+/// Compares the entries of a view with those of a `Matrix`
+impl<'a, T: Num + Clone + Copy> PartialEq<Matrix<T>> for MatrixView<'a, T> {
+    fn eq(&self, other: &Matrix<T>) -> bool {
+        *self == other.as_view()
+    }
+}
+
+/// Borrows the whole `Matrix` as a view
+impl<'a, T: Num + Clone + Copy> From<&'a Matrix<T>> for MatrixView<'a, T> {
+    fn from(matrix: &'a Matrix<T>) -> Self {
+        matrix.as_view()
+    }
+}
+
+/// Pretty print of the `MatrixView`, the same as that of `Matrix`
+impl<'a, T: Num + Clone + Copy + Display> Display for MatrixView<'a, T> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.to_matrix().fmt(fmt)
+    }
+}
+
+/// Pretty print of the `MatrixViewMut`, the same as that of `Matrix`
+impl<'a, T: Num + Clone + Copy + Display> Display for MatrixViewMut<'a, T> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.to_matrix().fmt(fmt)
+    }
+}
+
+// Implements an operator between two views, and between a view and a borrowed `Matrix` in either
+// order, panicking on mismatched sizes like the operators of `Matrix`
+macro_rules! impl_view_op {
+    ($($op:ident $method:ident $checked:ident;)*) => {$(
+        impl<'a, 'b, T: Num + Clone + Copy + Element> $op<MatrixView<'b, T>> for MatrixView<'a, T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: MatrixView<'b, T>) -> Matrix<T> {
+                self.$checked(&other).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl<'a, 'b, T: Num + Clone + Copy + Element> $op<&'b Matrix<T>> for MatrixView<'a, T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: &'b Matrix<T>) -> Matrix<T> {
+                self.$checked(&other.as_view()).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        impl<'a, 'b, T: Num + Clone + Copy + Element> $op<MatrixView<'b, T>> for &'a Matrix<T> {
+            type Output = Matrix<T>;
+
+            fn $method(self, other: MatrixView<'b, T>) -> Matrix<T> {
+                self.as_view().$checked(&other).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+    )*};
+}
+
+impl_view_op! {
+    Add add try_add;
+    Sub sub try_sub;
+    Mul mul try_mul;
+}
+
+#[cfg(test)]
+mod view_impl_tests {
+    use crate::Matrix;
+
+    #[test]
+    fn test_ops() {
+        let matrix = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+
+        assert_eq!(
+            matrix.as_view() + matrix.transpose_view(),
+            Matrix::from_vec(2, vec![2, 5, 5, 8])
+        );
+        assert_eq!(
+            matrix.row(0) - matrix.row(1),
+            Matrix::from_vec((1, 2), vec![-2, -2])
+        );
+        assert_eq!(
+            &matrix * matrix.col(1),
+            Matrix::from_vec((2, 1), vec![10, 22])
+        );
+        assert_eq!(
+            matrix.transpose_view(),
+            Matrix::from_vec(2, vec![1, 3, 2, 4])
+        );
+        assert_eq!(
+            matrix.as_view() + &matrix,
+            Matrix::from_vec(2, vec![2, 4, 6, 8])
+        );
+        assert_eq!(format!("{}", matrix.col(0)), "1 \n3 \n");
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 2) is out of bounds for a 2x2 matrix")]
+    fn test_index_out_of_bounds() {
+        let matrix = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+        let _ = matrix.as_view()[(0, 2)];
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch between a 1x2 and a 2x1 matrix")]
+    fn test_mismatched_add() {
+        let matrix = Matrix::from_vec(2, vec![1, 2, 3, 4]);
+        let _ = matrix.row(0) + matrix.col(0);
+    }
+}
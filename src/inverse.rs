@@ -0,0 +1,268 @@
+use crate::linalg::is_negligible;
+use crate::parallel::Element;
+use crate::MatrixError;
+
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+use num_traits::{Float, Num};
+
+/// Element types that a `Matrix` knows how to invert.
+///
+/// The provided method suits exact types: integers, and user defined rationals or big integers,
+/// which only need an empty `impl Inverse for MyType {}`. It uses fraction-free Gauss-Jordan
+/// elimination, so every intermediate division is exact, and a matrix is singular when it has no
+/// exact inverse. An integer matrix only has one when its determinant is `1` or `-1`. Floats (and,
+/// with the `complex` feature, complex floats) instead pivot on the largest entry of each column,
+/// and treat pivots that are negligibly small compared to the largest entry as zero. Primitive
+/// integers check every intermediate value for overflow, which for unsigned integers includes any
+/// value that would be negative.
+pub trait Inverse: Num + Copy + Element {
+    /// Calculate the inverse of the `n * n` matrix stored row by row in `data`, failing with
+    /// `MatrixError::Singular` if it has none, or `MatrixError::Overflow` if an intermediate value
+    /// overflows. The contents of `data` are used as scratch space. The provided method cannot
+    /// detect overflow.
+    fn inverse(data: &mut [Self], n: usize) -> Result<Vec<Self>, MatrixError> {
+        fraction_free(
+            data,
+            n,
+            |a, b| Some(a * b),
+            |a, b| Some(a - b),
+            |a, b| Some(a / b),
+        )
+    }
+}
+
+// The `n * n` identity matrix, stored row by row
+fn identity<T: Num + Copy>(n: usize) -> Vec<T> {
+    (0..n * n)
+        .map(|i| if i / n == i % n { T::one() } else { T::zero() })
+        .collect()
+}
+
+// Fraction-free Gauss-Jordan elimination on `[A | I]`. Every entry stays a minor of the augmented
+// matrix, so each division by the previous pivot is exact, and it ends as `[dI | dA⁻¹]` where `d`
+// is the determinant of `A` up to sign. The inverse is exact only if `d` divides every entry. Each
+// arithmetic operation goes through the given closures, which return `None` on overflow.
+fn fraction_free<T, M, S, D>(
+    mat: &mut [T],
+    n: usize,
+    mul: M,
+    sub: S,
+    div: D,
+) -> Result<Vec<T>, MatrixError>
+where
+    T: Num + Copy,
+    M: Fn(T, T) -> Option<T>,
+    S: Fn(T, T) -> Option<T>,
+    D: Fn(T, T) -> Option<T>,
+{
+    // `(a * pivot - lead * b) / prev`, the update of every entry outside the pivot row
+    let eliminate = |a, b, pivot, lead, prev| div(sub(mul(a, pivot)?, mul(lead, b)?)?, prev);
+
+    let mut inv = identity(n);
+    let mut prev = T::one();
+
+    for k in 0..n {
+        if mat[k * n + k].is_zero() {
+            let row = ((k + 1)..n)
+                .find(|&row| !mat[row * n + k].is_zero())
+                .ok_or(MatrixError::Singular)?;
+            for j in 0..n {
+                mat.swap(row * n + j, k * n + j);
+                inv.swap(row * n + j, k * n + j);
+            }
+        }
+
+        let pivot = mat[k * n + k];
+        for row in (0..n).filter(|&row| row != k) {
+            let lead = mat[row * n + k];
+            for j in 0..n {
+                mat[row * n + j] = eliminate(mat[row * n + j], mat[k * n + j], pivot, lead, prev)
+                    .ok_or(MatrixError::Overflow)?;
+                inv[row * n + j] = eliminate(inv[row * n + j], inv[k * n + j], pivot, lead, prev)
+                    .ok_or(MatrixError::Overflow)?;
+            }
+        }
+
+        prev = pivot;
+    }
+
+    let det = prev;
+    for entry in inv.iter_mut() {
+        let quotient = div(*entry, det).ok_or(MatrixError::Overflow)?;
+        if mul(quotient, det) != Some(*entry) {
+            return Err(MatrixError::Singular);
+        }
+        *entry = quotient;
+    }
+
+    Ok(inv)
+}
+
+// Gauss-Jordan elimination, swapping the largest remaining entry of each column (as measured by
+// `magnitude`) into the pivot. Pivots that are negligible next to the largest entry are treated as
+// zero.
+fn pivoted<T, R, M>(mat: &mut [T], n: usize, magnitude: M) -> Result<Vec<T>, MatrixError>
+where
+    T: Num + Copy,
+    R: Float,
+    M: Fn(T) -> R,
+{
+    let mut inv = identity(n);
+
+    let scale = mat.iter().fold(R::zero(), |acc, &x| acc.max(magnitude(x)));
+    let tolerance = R::epsilon() * R::from(n).unwrap_or_else(R::one) * scale;
+
+    for i in 0..n {
+        let mut pivot = i;
+        for row in (i + 1)..n {
+            if magnitude(mat[row * n + i]) > magnitude(mat[pivot * n + i]) {
+                pivot = row;
+            }
+        }
+
+        if is_negligible(magnitude(mat[pivot * n + i]), tolerance) {
+            return Err(MatrixError::Singular);
+        }
+
+        if pivot != i {
+            for j in 0..n {
+                mat.swap(pivot * n + j, i * n + j);
+                inv.swap(pivot * n + j, i * n + j);
+            }
+        }
+
+        let diag = mat[i * n + i];
+        for j in 0..n {
+            mat[i * n + j] = mat[i * n + j] / diag;
+            inv[i * n + j] = inv[i * n + j] / diag;
+        }
+
+        for row in (0..n).filter(|&row| row != i) {
+            let factor = mat[row * n + i];
+            if factor.is_zero() {
+                continue;
+            }
+
+            for j in 0..n {
+                mat[row * n + j] = mat[row * n + j] - factor * mat[i * n + j];
+                inv[row * n + j] = inv[row * n + j] - factor * inv[i * n + j];
+            }
+        }
+    }
+
+    Ok(inv)
+}
+
+macro_rules! impl_integer {
+    ($($t:ty)*) => {$(
+        impl Inverse for $t {
+            fn inverse(data: &mut [Self], n: usize) -> Result<Vec<Self>, MatrixError> {
+                fraction_free(data, n, <$t>::checked_mul, <$t>::checked_sub, <$t>::checked_div)
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_float {
+    ($($t:ty)*) => {$(
+        impl Inverse for $t {
+            fn inverse(data: &mut [Self], n: usize) -> Result<Vec<Self>, MatrixError> {
+                pivoted(data, n, <$t>::abs)
+            }
+        }
+    )*};
+}
+
+impl_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+impl_float!(f32 f64);
+
+#[cfg(feature = "complex")]
+impl<T: Float + Element> Inverse for Complex<T> {
+    fn inverse(data: &mut [Self], n: usize) -> Result<Vec<Self>, MatrixError> {
+        pivoted(data, n, |z: Self| z.norm())
+    }
+}
+
+#[cfg(test)]
+mod inverse_tests {
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_integers() {
+        // Unimodular, so its inverse is an integer matrix
+        let matrix = Matrix::from_vec(3, vec![2, 3, 1, 1, 2, 1, 1, 1, 1i64]);
+        let inverse = matrix.inverse().unwrap();
+
+        assert_eq!(&matrix * &inverse, Matrix::identity(3));
+        assert_eq!(
+            Matrix::from_vec(2, vec![0, 1, 1, 0]).inverse(),
+            Ok(Matrix::from_vec(2, vec![0, 1, 1, 0]))
+        );
+
+        // Invertible over the rationals, but not over the integers
+        assert_eq!(
+            Matrix::from_vec(2, vec![2, 0, 0, 2]).inverse(),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(
+            Matrix::from_vec(3, vec![1, 2, 3, 4, 5, 6, 7, 8, 9]).inverse(),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(Matrix::<i32>::zeros(0).inverse(), Ok(Matrix::zeros(0)));
+    }
+
+    #[test]
+    fn test_overflow() {
+        // The inverse, `[[1, 0], [-1, 1]]`, has a negative entry
+        assert_eq!(
+            Matrix::<u32>::from_vec(2, vec![1, 0, 1, 1]).inverse(),
+            Err(MatrixError::Overflow)
+        );
+        assert_eq!(
+            Matrix::<u32>::from_vec(2, vec![0, 1, 1, 0]).inverse(),
+            Ok(Matrix::from_vec(2, vec![0, 1, 1, 0]))
+        );
+
+        // The products of the second elimination step exceed `i32::MAX`
+        let entries = vec![997, 631, 1000, 512, 883, 100, 761, 409, 977];
+        assert_eq!(
+            Matrix::<i32>::from_vec(3, entries.clone()).inverse(),
+            Err(MatrixError::Overflow)
+        );
+        assert_eq!(
+            Matrix::<i64>::from_vec(3, entries.into_iter().map(i64::from).collect()).inverse(),
+            Err(MatrixError::Singular),
+            "not unimodular, so it has no integer inverse"
+        );
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn test_complex() {
+        use num_complex::Complex;
+
+        let matrix = Matrix::from_vec(
+            2,
+            vec![
+                Complex::new(1.0, 1.0),
+                Complex::new(2.0, 0.0),
+                Complex::new(0.0, 1.0),
+                Complex::new(1.0, -1.0),
+            ],
+        );
+        let product = &matrix * &matrix.inverse().unwrap();
+
+        for (entry, expected) in product
+            .as_slice()
+            .iter()
+            .zip(Matrix::<Complex<f64>>::identity(2).as_slice())
+        {
+            assert!((entry - expected).norm() < 1e-12);
+        }
+        assert_eq!(
+            Matrix::<Complex<f64>>::zeros(2).inverse(),
+            Err(MatrixError::Singular)
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::iter::{Enumerate, StepBy};
+use std::slice;
+
+use crate::Matrix;
+
+use num_traits::Num;
+
+/// An iterator over the rows of a `Matrix`, as slices. Created by `Matrix::rows`.
+#[derive(Clone, Debug)]
+pub struct Rows<'a, T> {
+    data: &'a [T],
+    width: usize,
+    remaining: usize,
+}
+
+/// An iterator over the rows of a `Matrix`, as mutable slices. Created by `Matrix::rows_mut`.
+#[derive(Debug)]
+pub struct RowsMut<'a, T> {
+    data: &'a mut [T],
+    width: usize,
+    remaining: usize,
+}
+
+/// An iterator over the columns of a `Matrix`, each of which is an iterator over its entries from
+/// top to bottom. Created by `Matrix::cols_iter`.
+#[derive(Clone, Debug)]
+pub struct Cols<'a, T> {
+    data: &'a [T],
+    width: usize,
+    next: usize,
+}
+
+/// An iterator over the entries of a `Matrix` along with their locations, in row-major order.
+/// Created by `Matrix::indexed_iter`.
+#[derive(Clone, Debug)]
+pub struct IndexedIter<'a, T> {
+    inner: Enumerate<slice::Iter<'a, T>>,
+    width: usize,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = &'a [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (row, rest) = self.data.split_at(self.width);
+        self.data = rest;
+        self.remaining -= 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Rows<'a, T> {}
+
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let (row, rest) = std::mem::take(&mut self.data).split_at_mut(self.width);
+        self.data = rest;
+        self.remaining -= 1;
+        Some(row)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RowsMut<'a, T> {}
+
+impl<'a, T> Iterator for Cols<'a, T> {
+    type Item = StepBy<slice::Iter<'a, T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next == self.width {
+            return None;
+        }
+
+        // A matrix with no rows has no data to offset into
+        let column = self.data.get(self.next..).unwrap_or(&[]);
+        self.next += 1;
+        Some(column.iter().step_by(self.width))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.width - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Cols<'a, T> {}
+
+impl<'a, T> Iterator for IndexedIter<'a, T> {
+    type Item = ((usize, usize), &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.width;
+        self.inner
+            .next()
+            .map(|(index, value)| ((index / width, index % width), value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IndexedIter<'a, T> {}
+
+impl<T: Num + Clone + Copy> Matrix<T> {
+    /// An iterator over the entries of the matrix, in row-major order
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(matrix.iter().sum::<i32>(), 10);
+    /// ```
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.data.iter()
+    }
+
+    /// A mutable iterator over the entries of the matrix, in row-major order
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let mut matrix = matrix![(2, 2); 1, 2; 3, 4];
+    /// matrix.iter_mut().for_each(|x| *x *= *x);
+    ///
+    /// assert_eq!(matrix, matrix![(2, 2); 1, 4; 9, 16]);
+    /// ```
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.data.iter_mut()
+    }
+
+    /// An iterator over the entries of the matrix along with their `(row, col)` locations, in
+    /// row-major order
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 2); 1, 2; 3, 4];
+    /// let mut iter = matrix.indexed_iter();
+    ///
+    /// assert_eq!(iter.next(), Some(((0, 0), &1)));
+    /// assert_eq!(iter.last(), Some(((1, 1), &4)));
+    /// ```
+    pub fn indexed_iter(&self) -> IndexedIter<'_, T> {
+        IndexedIter {
+            inner: self.data.iter().enumerate(),
+            width: self.width,
+        }
+    }
+
+    /// An iterator over the rows of the matrix, as slices
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// let sums: Vec<i32> = matrix.rows().map(|row| row.iter().sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![6, 15]);
+    /// ```
+    pub fn rows(&self) -> Rows<'_, T> {
+        Rows {
+            data: &self.data,
+            width: self.width,
+            remaining: self.height,
+        }
+    }
+
+    /// An iterator over the rows of the matrix, as mutable slices
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let mut matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// for row in matrix.rows_mut() {
+    ///     row.reverse();
+    /// }
+    ///
+    /// assert_eq!(matrix, matrix![(2, 3); 3, 2, 1; 6, 5, 4]);
+    /// ```
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            data: &mut self.data,
+            width: self.width,
+            remaining: self.height,
+        }
+    }
+
+    /// An iterator over the columns of the matrix, each of which iterates over its entries from
+    /// top to bottom without copying them
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// let sums: Vec<i32> = matrix.cols_iter().map(|col| col.sum()).collect();
+    ///
+    /// assert_eq!(sums, vec![5, 7, 9]);
+    /// ```
+    pub fn cols_iter(&self) -> Cols<'_, T> {
+        Cols {
+            data: &self.data,
+            width: self.width,
+            next: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod iter_tests {
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_iterators() {
+        let matrix = Matrix::from_vec((2, 3), vec![1, 2, 3, 4, 5, 6]);
+
+        assert_eq!(matrix.rows().len(), 2);
+        assert_eq!(matrix.rows().nth(1), Some(&[4, 5, 6][..]));
+        assert_eq!(
+            matrix
+                .cols_iter()
+                .map(|col| col.copied().collect())
+                .collect::<Vec<Vec<i32>>>(),
+            matrix.cols()
+        );
+        assert_eq!(
+            matrix
+                .indexed_iter()
+                .filter(|&(_, &x)| x % 2 == 0)
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 2)]
+        );
+        assert_eq!((&matrix).into_iter().count(), 6);
+        assert_eq!(
+            matrix.clone().into_iter().collect::<Vec<_>>(),
+            matrix.as_slice()
+        );
+    }
+
+    #[test]
+    fn test_degenerate_shapes() {
+        let no_rows: Matrix<i32> = Matrix::zeros((0, 3));
+        let no_cols: Matrix<i32> = Matrix::zeros((3, 0));
+
+        assert_eq!(no_rows.rows().count(), 0);
+        assert_eq!(no_rows.cols_iter().map(|col| col.count()).sum::<usize>(), 0);
+        assert_eq!(no_rows.cols_iter().len(), 3);
+        assert_eq!(no_cols.rows().len(), 3);
+        assert!(no_cols.rows().all(|row| row.is_empty()));
+        assert_eq!(no_cols.cols_iter().count(), 0);
+    }
+
+    #[test]
+    fn test_mutation() {
+        let mut matrix: Matrix<i32> = Matrix::zeros((2, 2));
+        for (i, row) in matrix.rows_mut().enumerate() {
+            row[i] = 1;
+        }
+        assert_eq!(matrix, Matrix::identity(2));
+
+        for x in &mut matrix {
+            *x += 1;
+        }
+        assert_eq!(matrix, Matrix::from_vec(2, vec![2, 1, 1, 2]));
+    }
+
+    #[test]
+    fn test_collect() {
+        let column: Matrix<i32> = (1..4).collect();
+        assert_eq!(column.size(), (3, 1));
+
+        assert_eq!(
+            Matrix::from_iter_shape((2, 2), 1..5),
+            Ok(Matrix::from_vec(2, vec![1, 2, 3, 4]))
+        );
+        assert_eq!(
+            Matrix::from_iter_shape((2, 2), 1..4),
+            Err(MatrixError::InvalidLength {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Matrix::from_iter_shape(1, 1..4),
+            Err(MatrixError::InvalidLength {
+                expected: 1,
+                found: 3
+            })
+        );
+    }
+}
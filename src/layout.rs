@@ -0,0 +1,316 @@
+use std::fmt::{self, Debug, Display, Formatter};
+use std::marker::PhantomData;
+
+use crate::parallel::Element;
+use crate::size::Size;
+use crate::{gemm, Matrix, MatrixError};
+
+use num_traits::Num;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::RowMajor {}
+    impl Sealed for super::ColMajor {}
+}
+
+/// The order in which the entries of a `Matrix` are stored in its flat buffer, either `RowMajor`
+/// (the default) or `ColMajor`. This trait is sealed, so it cannot be implemented outside of the
+/// crate.
+pub trait Layout: Clone + Copy + Debug + Default + PartialEq + Eq + private::Sealed {
+    /// The distances in storage between two vertically adjacent entries and between two
+    /// horizontally adjacent entries of a `height * width` matrix
+    fn strides(height: usize, width: usize) -> (usize, usize);
+}
+
+/// Entries are stored one row after another, as in C and the default order of NumPy
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RowMajor;
+
+/// Entries are stored one column after another, as in Fortran, LAPACK and NumPy's `order='F'`.
+/// Columns are then contiguous slices, see `Matrix::col_slice`.
+/// ```
+/// use mtrs::{ColMajor, Matrix};
+///
+/// let matrix: Matrix<i32, ColMajor> = Matrix::from_col_major((2, 3), vec![1, 4, 2, 5, 3, 6]);
+///
+/// assert_eq!(matrix[(0, 1)], 2);
+/// assert_eq!(matrix.col_slice(2), Some(&[3, 6][..]));
+/// assert_eq!(matrix.to_row_major().as_slice(), &[1, 2, 3, 4, 5, 6]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ColMajor;
+
+impl Layout for RowMajor {
+    fn strides(_height: usize, width: usize) -> (usize, usize) {
+        (width, 1)
+    }
+}
+
+impl Layout for ColMajor {
+    fn strides(height: usize, _width: usize) -> (usize, usize) {
+        (1, height)
+    }
+}
+
+impl<T: Num + Clone + Copy, L: Layout> Matrix<T, L> {
+    /// Copy the matrix into a new row-major matrix
+    pub fn to_row_major(&self) -> Matrix<T> {
+        self.as_view().to_matrix()
+    }
+
+    /// Copy the matrix into a new column-major matrix
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 2); 1, 2; 3, 4].to_col_major();
+    ///
+    /// assert_eq!(matrix.as_slice(), &[1, 3, 2, 4]);
+    /// assert_eq!(matrix.to_row_major(), matrix![(2, 2); 1, 2; 3, 4]);
+    /// ```
+    pub fn to_col_major(&self) -> Matrix<T, ColMajor> {
+        // The rows of the transpose are the columns of the matrix
+        let transposed = self.transpose_view().to_matrix();
+        Matrix::from_col_major(self.size(), transposed.data)
+    }
+}
+
+impl<T: Num + Clone + Copy> Matrix<T, ColMajor> {
+    /// Creates a new column-major matrix of the given size from its entries listed column by
+    /// column, such as a Fortran array, without copying them
+    pub fn from_col_major<S: Size>(size: S, body: Vec<T>) -> Self {
+        let (height, width) = size.dim();
+        debug_assert_eq!(body.len(), height * width, "Invalid length for matrix body");
+        Matrix {
+            height,
+            width,
+            data: body,
+            layout: PhantomData,
+        }
+    }
+
+    /// Creates a new column-major matrix of the given size from its entries listed column by
+    /// column, checking that the body has the right length
+    /// ```
+    /// use mtrs::{Matrix, MatrixError};
+    ///
+    /// assert!(Matrix::try_from_col_major((2, 2), vec![1, 3, 2, 4]).is_ok());
+    /// assert_eq!(
+    ///     Matrix::try_from_col_major((2, 2), vec![1, 3, 2]),
+    ///     Err(MatrixError::InvalidLength { expected: 4, found: 3 })
+    /// );
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if the length of `body` is not `height * width`
+    pub fn try_from_col_major<S: Size>(size: S, body: Vec<T>) -> Result<Self, MatrixError> {
+        let (height, width) = size.dim();
+        if body.len() != height * width {
+            Err(MatrixError::InvalidLength {
+                expected: height * width,
+                found: body.len(),
+            })
+        } else {
+            Ok(Self::from_col_major((height, width), body))
+        }
+    }
+
+    /// Borrow a column as a contiguous slice, or `None` if it is out of bounds
+    pub fn col_slice(&self, index: usize) -> Option<&[T]> {
+        if index < self.width {
+            Some(&self.data[index * self.height..(index + 1) * self.height])
+        } else {
+            None
+        }
+    }
+
+    /// Mutably borrow a column as a contiguous slice, or `None` if it is out of bounds
+    pub fn col_slice_mut(&mut self, index: usize) -> Option<&mut [T]> {
+        if index < self.width {
+            Some(&mut self.data[index * self.height..(index + 1) * self.height])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Num + Clone + Copy + Element> Matrix<T, ColMajor> {
+    /// Converts the matrix to row-major storage in place, see `Matrix::transpose`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let matrix = Matrix::from_col_major((2, 3), vec![1, 4, 2, 5, 3, 6]);
+    ///
+    /// assert_eq!(matrix.into_row_major(), matrix![(2, 3); 1, 2, 3; 4, 5, 6]);
+    /// ```
+    pub fn into_row_major(self) -> Matrix<T> {
+        // The storage is that of the row-major transpose
+        let mut matrix = Matrix::from_vec((self.width, self.height), self.data);
+        matrix.transpose();
+        matrix
+    }
+
+    /// Multiply two column-major matrices, where the width of `self` must equal the height of
+    /// `other`, see `Matrix::try_mul`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let m1 = matrix![(2, 3); 2, 3, 4; 1, 0, 0].to_col_major();
+    /// let m2 = matrix![(3, 2); 0, 1000; 1, 100; 0, 10].to_col_major();
+    ///
+    /// assert_eq!(m1.try_mul(&m2).unwrap(), matrix![(2, 2); 3, 2340; 0, 1000]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let (height, width) = (self.height, other.width);
+        let mut product = Self::from_col_major((height, width), vec![T::zero(); height * width]);
+        gemm::kernel(
+            T::one(),
+            &self.as_view(),
+            &other.as_view(),
+            T::zero(),
+            &mut product.as_view_mut(),
+        );
+
+        Ok(product)
+    }
+}
+
+impl<T: Num + Clone + Copy + Element> Matrix<T> {
+    /// Converts the matrix to column-major storage in place, see `Matrix::transpose`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6].into_col_major();
+    ///
+    /// assert_eq!(matrix.as_slice(), &[1, 4, 2, 5, 3, 6]);
+    /// ```
+    pub fn into_col_major(mut self) -> Matrix<T, ColMajor> {
+        let size = self.size();
+        self.transpose();
+        Matrix::from_col_major(size, self.data)
+    }
+}
+
+/// Converts a column-major matrix to row-major storage in place
+impl<T: Num + Clone + Copy + Element> From<Matrix<T, ColMajor>> for Matrix<T> {
+    fn from(matrix: Matrix<T, ColMajor>) -> Self {
+        matrix.into_row_major()
+    }
+}
+
+/// Converts a row-major matrix to column-major storage in place
+impl<T: Num + Clone + Copy + Element> From<Matrix<T>> for Matrix<T, ColMajor> {
+    fn from(matrix: Matrix<T>) -> Self {
+        matrix.into_col_major()
+    }
+}
+
+/// Matrices with different layouts are equal when they have the same size and entries
+impl<T: Num + Clone + Copy> PartialEq<Matrix<T, ColMajor>> for Matrix<T> {
+    fn eq(&self, other: &Matrix<T, ColMajor>) -> bool {
+        self.as_view() == other.as_view()
+    }
+}
+
+/// Matrices with different layouts are equal when they have the same size and entries
+impl<T: Num + Clone + Copy> PartialEq<Matrix<T>> for Matrix<T, ColMajor> {
+    fn eq(&self, other: &Matrix<T>) -> bool {
+        self.as_view() == other.as_view()
+    }
+}
+
+/// Pretty print of a column-major `Matrix`, the same as that of a row-major one
+impl<T: Num + Clone + Copy + Display> Display for Matrix<T, ColMajor> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.to_row_major().fmt(fmt)
+    }
+}
+
+#[cfg(test)]
+mod layout_tests {
+    use crate::test_util::sample;
+    use crate::{ColMajor, Matrix, MatrixError};
+
+    #[test]
+    fn test_conversions() {
+        for &size in &[(0, 0), (0, 3), (1, 4), (3, 1), (3, 5), (6, 6)] {
+            let matrix = sample(size, 0);
+            let col_major = matrix.to_col_major();
+
+            assert_eq!(col_major.size(), size);
+            assert_eq!(col_major.as_slice(), matrix.transposed().as_slice());
+            assert_eq!(col_major, matrix);
+            assert_eq!(matrix.clone().into_col_major(), col_major);
+            assert_eq!(Matrix::<i64, ColMajor>::from(matrix.clone()), col_major);
+            assert_eq!(col_major.to_row_major(), matrix);
+            assert_eq!(col_major.clone().into_row_major(), matrix);
+            assert_eq!(Matrix::<i64>::from(col_major), matrix);
+        }
+    }
+
+    #[test]
+    fn test_access() {
+        let mut matrix = Matrix::from_col_major((2, 3), vec![1, 4, 2, 5, 3, 6]);
+
+        assert_eq!(matrix.get((1, 2)), Some(&6));
+        assert_eq!(matrix.get((2, 0)), None);
+        assert_eq!(matrix.col_slice(1), Some(&[2, 5][..]));
+        assert_eq!(matrix.col_slice(3), None);
+        assert_eq!(
+            matrix.row(1).to_matrix(),
+            Matrix::from_vec((1, 3), vec![4, 5, 6])
+        );
+
+        matrix.set((0, 2), 9).unwrap();
+        matrix.col_slice_mut(0).unwrap()[1] = 7;
+        matrix.view_mut(1, 1..).fill(0);
+        assert_eq!(matrix.as_slice(), &[1, 7, 2, 0, 9, 0]);
+        assert_eq!(format!("{}", matrix), "1 2 9 \n7 0 0 \n");
+
+        assert_eq!(
+            Matrix::<i32, ColMajor>::try_from_col_major((2, 2), vec![]),
+            Err(MatrixError::InvalidLength {
+                expected: 4,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_mul() {
+        let (a, b) = (sample((7, 5), 1), sample((5, 9), 2));
+
+        assert_eq!(
+            a.to_col_major().try_mul(&b.to_col_major()),
+            Ok((&a * &b).to_col_major())
+        );
+        assert_eq!(
+            a.to_col_major().try_mul(&a.to_col_major()),
+            Err(MatrixError::DimensionMismatch {
+                left: (7, 5),
+                right: (7, 5)
+            })
+        );
+    }
+
+    #[test]
+    fn test_qr() {
+        let a: Matrix<f64> = Matrix::from_vec(
+            (4, 3),
+            vec![2.0, -1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 4.0, 1.0, 0.0, -2.0],
+        );
+        let (row_major, col_major) = (a.qr(), a.to_col_major().qr());
+
+        assert_eq!(row_major.q(), col_major.q());
+        assert_eq!(row_major.r(), col_major.r());
+    }
+}
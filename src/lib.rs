@@ -1,6 +1,7 @@
 //! A library for creating, using, and printing matrices.
 //! Matrices can be defined using the public struct, `mtrs::Matrix`, or the macro `matrix!`.
-//! Currently the `Matrix` struct does not internally support floats. This may change in the future.
+//! Entries can be any `num_traits::Num` type, such as integers or floats. Determinants and inverses
+//! of integer matrices are exact, while the decompositions and solvers work on floats.
 //! ```
 //! #[macro_use] extern crate mtrs;
 //! use mtrs::Matrix;
@@ -12,17 +13,96 @@
 //! ```
 //! The `Matrix` struct supports addition, subtraction, and multiplication with eachother,
 //! along with implementations for basic operations between a scalar value and a `Matrix`
+//!
+//! Small matrices whose size is known at compile time can be an `SMatrix<T, R, C>` instead, which
+//! is stored on the stack and only compiles products of matching sizes. It converts to and from
+//! `Matrix`, and borrows as a `MatrixView` for the decompositions.
+//!
+//! Mostly zero matrices can be assembled as a `CooMatrix`, then converted to a `CsrMatrix` or
+//! `CscMatrix` for products and sums, which only store the non-zero entries. Large sparse systems
+//! are solved iteratively with `CsrMatrix::cg`, `bicgstab` or `gmres`, configured by
+//! `SolverOptions`.
+//!
+//! The Krylov solvers `cg`, `bicgstab` and `gmres` and the eigenvalue methods `power_iteration`
+//! and `lanczos` only ever apply their operator to vectors, so they accept any `LinearOperator`:
+//! dense and sparse matrices, views, closures wrapped in an `FnOperator`, or operators that are
+//! never stored at all.
+//!
+//! The `complex` feature pulls in `num-complex`. It adds the general eigenvalue solver
+//! (`Matrix::eigenvalues` and `Matrix::eigen`) and its complex results, determinants of complex
+//! matrices, and the adjoint, Hermitian check, modulus based norms and spectral norm of
+//! `Matrix<Complex<T>>`.
+//!
+//! The `rayon` feature splits multiplication, elementwise and scalar operations, `transposed` and
+//! the row reduction of determinants and LU decompositions between threads once matrices are large
+//! enough. Integer results are identical to the serial ones. Entries are always required to be
+//! `Send + Sync` (see `Element`), so enabling the feature never rules out an element type. The work
+//! runs on the current `rayon` thread pool, so `ThreadPool::install` controls how many threads are
+//! used:
+//! ```
+//! # #[cfg(feature = "rayon")] {
+//! # use mtrs::Matrix;
+//! # let (a, b) = (Matrix::<f64>::identity(200), Matrix::identity(200));
+//! let pool = rayon::ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+//! let product = pool.install(|| &a * &b);
+//! # assert_eq!(product, a);
+//! # }
+//! ```
 
+#[cfg(feature = "complex")]
+extern crate num_complex;
 extern crate num_traits;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
+#[cfg(feature = "complex")]
+mod complex;
+mod determinant;
+mod error;
+mod gemm;
 mod impls;
+mod inverse;
+mod iter;
+mod layout;
+mod linalg;
 mod macros;
 mod math;
 mod matrix;
+mod operator;
+mod parallel;
 mod size;
+mod smatrix;
+mod sparse;
+mod strassen;
+#[cfg(test)]
+mod test_util;
+mod view;
+
+use std::marker::PhantomData;
 
 use num_traits::Num;
 
+pub use determinant::Determinant;
+pub use error::MatrixError;
+pub use gemm::gemm;
+pub use inverse::Inverse;
+pub use iter::{Cols, IndexedIter, Rows, RowsMut};
+pub use layout::{ColMajor, Layout, RowMajor};
+#[cfg(feature = "complex")]
+pub use linalg::Eigen;
+pub use linalg::{
+    bicgstab, cg, gmres, lanczos, power_iteration, Cholesky, IterativeSolution, Preconditioner,
+    Schur, SolverOptions, SymmetricEigen, LDLT, LU, QR, SVD,
+};
+#[cfg(feature = "complex")]
+pub use num_complex::Complex;
+pub use operator::{FnOperator, LinearOperator};
+pub use parallel::Element;
+pub use smatrix::SMatrix;
+pub use sparse::{CooMatrix, CscMatrix, CsrMatrix};
+pub use strassen::STRASSEN_THRESHOLD;
+pub use view::{MatrixView, MatrixViewMut};
+
 /// The main Matrix struct. Can be created in a variety of different ways.
 /// ```
 /// #[macro_use] extern crate mtrs;
@@ -43,8 +123,12 @@ use num_traits::Num;
 /// // Matrix of `f64`s
 /// let matrix = matrix![f64; (2, 2); 1, 2; 3, 4];
 /// ```
+///
+/// Entries are stored row by row unless the layout parameter is `ColMajor`, see `Layout`.
+/// Column-major matrices share the accessors, views and conversions of row-major ones, while
+/// most arithmetic and decompositions are only implemented for the row-major default.
 #[derive(Clone, PartialEq, Debug)]
-pub struct Matrix<T: Num> {
+pub struct Matrix<T: Num, L: Layout = RowMajor> {
     /// The height of the matrix
     height: usize,
 
@@ -53,6 +137,9 @@ pub struct Matrix<T: Num> {
 
     /// The main body of the matrix, stored as a 2d array
     data: Vec<T>,
+
+    /// The order of the entries in `data`
+    layout: PhantomData<L>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,384 @@
+use super::triangular::{back_substitute_transpose, forward_substitute};
+use super::{is_negligible, pivot_tolerance};
+use crate::{Matrix, MatrixError};
+
+use num_traits::{Float, Num};
+
+/// The Cholesky decomposition `A = LLᵀ` of a symmetric positive definite matrix, where `L` is
+/// lower triangular with a positive diagonal.
+///
+/// The factor can be updated in place when `A` changes by a rank-1 term, which costs `O(n²)`
+/// rather than the `O(n³)` of factoring again.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (2, 2); 4, 2; 2, 5];
+/// let cholesky = a.cholesky().unwrap();
+///
+/// assert_eq!(cholesky.l(), matrix![f64; (2, 2); 2, 0; 1, 2]);
+/// assert_eq!(cholesky.determinant(), 16.0);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Cholesky<T: Num> {
+    /// The lower triangular factor
+    l: Matrix<T>,
+}
+
+/// The `LDLᵀ` decomposition of a symmetric matrix, where `L` is unit lower triangular and `D` is
+/// diagonal. Unlike `Cholesky`, this does not require the matrix to be positive definite.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (2, 2); 1, 2; 2, 1];
+/// let ldlt = a.ldlt().unwrap();
+///
+/// assert_eq!(ldlt.l(), matrix![f64; (2, 2); 1, 0; 2, 1]);
+/// assert_eq!(ldlt.d(), &[1.0, -3.0]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct LDLT<T: Num> {
+    /// The unit lower triangular factor (its diagonal is stored as `1`s)
+    l: Matrix<T>,
+
+    /// The diagonal of `D`
+    d: Vec<T>,
+}
+
+// Computes the lower triangular `L` with `A = LLᵀ` from the lower triangle of the `n * n` matrix
+// `a`, stored row by row. Fails with the index of the first pivot that is not positive.
+pub(crate) fn cholesky_factor<T: Float>(a: &[T], n: usize, tolerance: T) -> Result<Vec<T>, usize> {
+    let mut l = vec![T::zero(); n * n];
+
+    for j in 0..n {
+        let mut diag = a[j * n + j];
+        for k in 0..j {
+            diag = diag - l[j * n + k] * l[j * n + k];
+        }
+
+        if diag <= T::zero() || is_negligible(diag, tolerance) {
+            return Err(j);
+        }
+
+        let diag = diag.sqrt();
+        l[j * n + j] = diag;
+
+        for i in (j + 1)..n {
+            let mut sum = a[i * n + j];
+            for k in 0..j {
+                sum = sum - l[i * n + k] * l[j * n + k];
+            }
+
+            l[i * n + j] = sum / diag;
+        }
+    }
+
+    Ok(l)
+}
+
+impl<T: Float> Matrix<T> {
+    /// Compute the Cholesky decomposition of a symmetric positive definite matrix.
+    /// Only the lower triangle of the matrix is read.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::MatrixError;
+    ///
+    /// let indefinite = matrix![f64; (2, 2); 1, 2; 2, 1];
+    ///
+    /// assert_eq!(indefinite.cholesky(), Err(MatrixError::NotPositiveDefinite { pivot: 1 }));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::NotPositiveDefinite` naming the first pivot that is not positive
+    pub fn cholesky(&self) -> Result<Cholesky<T>, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.height;
+        cholesky_factor(&self.data, n, pivot_tolerance(&self.data, n))
+            .map(|l| Cholesky {
+                l: Self::from_vec(n, l),
+            })
+            .map_err(|pivot| MatrixError::NotPositiveDefinite { pivot })
+    }
+
+    /// Compute the `LDLᵀ` decomposition of a symmetric matrix, without pivoting.
+    /// Only the lower triangle of the matrix is read.
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::Singular` if one of the pivots (the entries of `D`) is zero
+    pub fn ldlt(&self) -> Result<LDLT<T>, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.height;
+        let a = &self.data;
+        let tolerance = pivot_tolerance(a, n);
+        let mut l = Self::identity(n);
+        let mut d = vec![T::zero(); n];
+
+        for j in 0..n {
+            let mut diag = a[j * n + j];
+            for (k, &dk) in d.iter().enumerate().take(j) {
+                diag = diag - l.data[j * n + k] * l.data[j * n + k] * dk;
+            }
+
+            if is_negligible(diag, tolerance) {
+                return Err(MatrixError::Singular);
+            }
+
+            d[j] = diag;
+
+            for i in (j + 1)..n {
+                let mut sum = a[i * n + j];
+                for (k, &dk) in d.iter().enumerate().take(j) {
+                    sum = sum - l.data[i * n + k] * l.data[j * n + k] * dk;
+                }
+
+                l.data[i * n + j] = sum / diag;
+            }
+        }
+
+        Ok(LDLT { l, d })
+    }
+}
+
+// Checks that `b` is a valid right hand side for an `n * n` system
+fn check_rhs<T: Num>(n: usize, b: &Matrix<T>) -> Result<(), MatrixError> {
+    if b.height != n {
+        Err(MatrixError::DimensionMismatch {
+            left: (n, n),
+            right: (b.height, b.width),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+impl<T: Float> Cholesky<T> {
+    /// The lower triangular factor `L`
+    pub fn l(&self) -> Matrix<T> {
+        self.l.clone()
+    }
+
+    /// The determinant of the factored matrix
+    pub fn determinant(&self) -> T {
+        let n = self.l.height;
+        let det = (0..n).fold(T::one(), |acc, i| acc * self.l.data[i * n + i]);
+        det * det
+    }
+
+    /// Solve `AX = B` for `X` with a forward and a back substitution, where each column of `b`
+    /// is a separate right hand side
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let cholesky = matrix![f64; (2, 2); 4, 2; 2, 5].cholesky().unwrap();
+    ///
+    /// assert_eq!(cholesky.solve(&matrix![f64; (2, 1); 6; 7]), Ok(matrix![f64; (2, 1); 1; 1]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `b` does not have as many rows as `A`
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.l.height;
+        check_rhs(n, b)?;
+
+        let mut x = b.data.clone();
+        forward_substitute(&self.l.data, n, &mut x, b.width, false);
+        back_substitute_transpose(&self.l.data, n, &mut x, b.width, false);
+
+        Ok(Matrix::from_vec(b.size(), x))
+    }
+
+    /// The inverse of the factored matrix
+    pub fn inverse(&self) -> Matrix<T> {
+        self.solve(&Matrix::identity(self.l.height))
+            .expect("The identity has the right size")
+    }
+
+    /// Update the factorization of `A` to one of `A + xxᵀ`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let mut cholesky = matrix![f64; (2, 2); 4, 2; 2, 5].cholesky().unwrap();
+    /// cholesky.rank_one_update(&[0.0, 2.0]).unwrap();
+    ///
+    /// assert_eq!(cholesky, matrix![f64; (2, 2); 4, 2; 2, 9].cholesky().unwrap());
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `x` does not have one entry per row
+    pub fn rank_one_update(&mut self, x: &[T]) -> Result<(), MatrixError> {
+        self.rank_one(x, T::one())
+    }
+
+    /// Update the factorization of `A` to one of `A - xxᵀ`. The factor is left unchanged if
+    /// this fails.
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `x` does not have one entry per row, and
+    /// with `MatrixError::NotPositiveDefinite` if `A - xxᵀ` is not positive definite
+    pub fn rank_one_downdate(&mut self, x: &[T]) -> Result<(), MatrixError> {
+        self.rank_one(x, -T::one())
+    }
+
+    // Shared implementation of the update (`sign = 1`) and downdate (`sign = -1`)
+    fn rank_one(&mut self, x: &[T], sign: T) -> Result<(), MatrixError> {
+        let n = self.l.height;
+        if x.len() != n {
+            return Err(MatrixError::DimensionMismatch {
+                left: (n, n),
+                right: (x.len(), 1),
+            });
+        }
+
+        let mut l = self.l.data.clone();
+        let mut x = x.to_vec();
+
+        for k in 0..n {
+            let diag = l[k * n + k];
+            let squared = diag * diag + sign * x[k] * x[k];
+            if squared <= T::zero() || squared.is_nan() {
+                return Err(MatrixError::NotPositiveDefinite { pivot: k });
+            }
+
+            let r = squared.sqrt();
+            let c = r / diag;
+            let s = x[k] / diag;
+            l[k * n + k] = r;
+
+            for i in (k + 1)..n {
+                l[i * n + k] = (l[i * n + k] + sign * s * x[i]) / c;
+                x[i] = c * x[i] - s * l[i * n + k];
+            }
+        }
+
+        self.l.data = l;
+        Ok(())
+    }
+}
+
+impl<T: Float> LDLT<T> {
+    /// The unit lower triangular factor `L`
+    pub fn l(&self) -> Matrix<T> {
+        self.l.clone()
+    }
+
+    /// The diagonal of `D`
+    pub fn d(&self) -> &[T] {
+        &self.d
+    }
+
+    /// The determinant of the factored matrix
+    pub fn determinant(&self) -> T {
+        self.d.iter().fold(T::one(), |acc, &d| acc * d)
+    }
+
+    /// Solve `AX = B` for `X`, where each column of `b` is a separate right hand side
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `b` does not have as many rows as `A`
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.l.height;
+        check_rhs(n, b)?;
+
+        let k = b.width;
+        let mut x = b.data.clone();
+        forward_substitute(&self.l.data, n, &mut x, k, true);
+        for (i, &d) in self.d.iter().enumerate() {
+            for value in &mut x[i * k..(i + 1) * k] {
+                *value = *value / d;
+            }
+        }
+        back_substitute_transpose(&self.l.data, n, &mut x, k, true);
+
+        Ok(Matrix::from_vec(b.size(), x))
+    }
+}
+
+#[cfg(test)]
+mod cholesky_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    fn spd() -> Matrix<f64> {
+        Matrix::from_vec(
+            3,
+            vec![4.0, 12.0, -16.0, 12.0, 37.0, -43.0, -16.0, -43.0, 98.0],
+        )
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = spd();
+        let cholesky = a.cholesky().unwrap();
+        let mut l_t = cholesky.l();
+        l_t.transpose();
+
+        assert_eq!(
+            cholesky.l(),
+            Matrix::from_vec(3, vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0])
+        );
+        assert_close(&(cholesky.l() * l_t), &a);
+        assert_close(&(a.clone() * cholesky.inverse()), &Matrix::identity(3));
+
+        let b = Matrix::from_vec((3, 2), vec![1.0, 0.0, 2.0, 1.0, 3.0, -1.0]);
+        assert_close(&(a * cholesky.solve(&b).unwrap()), &b);
+    }
+
+    #[test]
+    fn test_rank_one() {
+        let x = [1.0, -2.0, 0.5];
+        let xxt = Matrix::from_vec(3, vec![1.0, -2.0, 0.5, -2.0, 4.0, -1.0, 0.5, -1.0, 0.25]);
+
+        let mut cholesky = spd().cholesky().unwrap();
+        cholesky.rank_one_update(&x).unwrap();
+        assert_close(
+            &cholesky.l(),
+            &(spd() + xxt.clone()).cholesky().unwrap().l(),
+        );
+
+        cholesky.rank_one_downdate(&x).unwrap();
+        assert_close(&cholesky.l(), &spd().cholesky().unwrap().l());
+
+        // Downdating by `x` with `xᵀA⁻¹x >= 1` leaves a matrix that is not positive definite
+        let before = cholesky.clone();
+        assert_eq!(
+            cholesky.rank_one_downdate(&[2.0, 0.0, 0.0]),
+            Err(MatrixError::NotPositiveDefinite { pivot: 0 })
+        );
+        assert_eq!(cholesky, before);
+    }
+
+    #[test]
+    fn test_ldlt() {
+        let a = Matrix::from_vec(3, vec![1.0, 2.0, 3.0, 2.0, -1.0, 0.0, 3.0, 0.0, 2.0]);
+        let ldlt = a.ldlt().unwrap();
+        let mut l_t = ldlt.l();
+        l_t.transpose();
+
+        assert_close(&(ldlt.l() * Matrix::diag(ldlt.d().to_vec()) * l_t), &a);
+        assert!((ldlt.determinant() - a.determinant().unwrap()).abs() < 1e-10);
+
+        let b = Matrix::from_vec((3, 1), vec![1.0, 2.0, 3.0]);
+        assert_close(&(a * ldlt.solve(&b).unwrap()), &b);
+    }
+
+    #[test]
+    fn test_failures() {
+        let zero_pivot = Matrix::from_vec(2, vec![0.0, 1.0, 1.0, 0.0]);
+
+        assert_eq!(
+            zero_pivot.cholesky(),
+            Err(MatrixError::NotPositiveDefinite { pivot: 0 })
+        );
+        assert_eq!(zero_pivot.ldlt(), Err(MatrixError::Singular));
+        assert_eq!(
+            Matrix::<f64>::zeros((2, 3)).cholesky(),
+            Err(MatrixError::NotSquare)
+        );
+        assert!(spd()
+            .cholesky()
+            .unwrap()
+            .solve(&Matrix::zeros((2, 1)))
+            .is_err());
+    }
+}
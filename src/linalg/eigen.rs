@@ -0,0 +1,366 @@
+use super::schur::{hessenberg_norm, reduce, Grid, Reduction, Schur};
+use crate::{Matrix, MatrixError};
+
+use num_complex::Complex;
+use num_traits::{Float, Num};
+
+/// The eigendecomposition `AV = VΛ` of a general square matrix, computed from its real Schur
+/// form.
+///
+/// The eigenvalues are complex in general, with complex eigenvalues of a real matrix coming in
+/// conjugate pairs. They are listed in the order they appear on the diagonal of the Schur form,
+/// and column `i` of `V` is a unit eigenvector for eigenvalue `i`.
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::Complex;
+///
+/// let eigen = matrix![f64; (2, 2); 2, 1; 0, 3].eigen().unwrap();
+///
+/// assert_eq!(eigen.eigenvalues(), &[Complex::new(2.0, 0.0), Complex::new(3.0, 0.0)]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Eigen<T: Num + Clone> {
+    /// The eigenvalues, in Schur order
+    eigenvalues: Vec<Complex<T>>,
+
+    /// The eigenvectors, as columns
+    eigenvectors: Matrix<Complex<T>>,
+}
+
+fn eigenvalues_from_parts<T: Float>(re: &[T], im: &[T]) -> Vec<Complex<T>> {
+    re.iter()
+        .zip(im)
+        .map(|(&re, &im)| Complex::new(re, im))
+        .collect()
+}
+
+impl<T: Float> Matrix<T> {
+    /// The eigenvalues of a square matrix, in the order they appear on the diagonal of its real
+    /// Schur form
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Complex;
+    ///
+    /// // A rotation by 90 degrees
+    /// let eigenvalues = matrix![f64; (2, 2); 0, -1; 1, 0].eigenvalues().unwrap();
+    ///
+    /// assert_eq!(eigenvalues, vec![Complex::new(0.0, 1.0), Complex::new(0.0, -1.0)]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::NotConverged` if the QR algorithm does not converge
+    pub fn eigenvalues(&self) -> Result<Vec<Complex<T>>, MatrixError> {
+        let reduction = reduce(self)?;
+        Ok(eigenvalues_from_parts(&reduction.re, &reduction.im))
+    }
+
+    /// Compute the eigenvalues and eigenvectors of a square matrix
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::NotConverged` if the QR algorithm does not converge
+    pub fn eigen(&self) -> Result<Eigen<T>, MatrixError> {
+        let reduction = reduce(self)?;
+        let n = reduction.h.n;
+        let eigenvalues = eigenvalues_from_parts(&reduction.re, &reduction.im);
+        let v = back_substitute(reduction);
+
+        // Real eigenvalues own one real column of `v`, and each complex pair shares two columns
+        // holding the real and imaginary parts of the first vector
+        let mut vectors = vec![Complex::new(T::zero(), T::zero()); n * n];
+        let mut j = 0;
+        while j < n {
+            if eigenvalues[j].im.is_zero() {
+                for i in 0..n {
+                    vectors[i * n + j] = Complex::new(v[(i, j)], T::zero());
+                }
+                j += 1;
+            } else {
+                for i in 0..n {
+                    let x = Complex::new(v[(i, j)], v[(i, j + 1)]);
+                    vectors[i * n + j] = x;
+                    vectors[i * n + j + 1] = x.conj();
+                }
+                j += 2;
+            }
+        }
+
+        for j in 0..n {
+            let norm = (0..n)
+                .fold(T::zero(), |acc, i| acc + vectors[i * n + j].norm_sqr())
+                .sqrt();
+            if !norm.is_zero() {
+                for i in 0..n {
+                    vectors[i * n + j] = vectors[i * n + j] / norm;
+                }
+            }
+        }
+
+        Ok(Eigen {
+            eigenvalues,
+            eigenvectors: Matrix::from_vec(n, vectors),
+        })
+    }
+}
+
+impl<T: Float> Schur<T> {
+    /// The eigenvalues, in the order they appear on the diagonal of `T`
+    pub fn eigenvalues(&self) -> Vec<Complex<T>> {
+        let (re, im) = self.eigenvalue_parts();
+        eigenvalues_from_parts(re, im)
+    }
+}
+
+impl<T: Float> Eigen<T> {
+    /// The eigenvalues, in the order they appear on the diagonal of the real Schur form
+    pub fn eigenvalues(&self) -> &[Complex<T>] {
+        &self.eigenvalues
+    }
+
+    /// The unit eigenvectors, where column `i` belongs to `eigenvalues()[i]`
+    pub fn eigenvectors(&self) -> Matrix<Complex<T>> {
+        self.eigenvectors.clone()
+    }
+}
+
+// Solves for the eigenvectors of the quasi upper triangular Schur form by back substitution and
+// maps them back through the Schur vectors. Adapted from the `hqr2` routine of EISPACK, by way of
+// JAMA.
+fn back_substitute<T: Float>(reduction: Reduction<T>) -> Grid<T> {
+    let Reduction {
+        mut h,
+        mut v,
+        re,
+        im,
+    } = reduction;
+    let norm = hessenberg_norm(&h);
+    let nn = h.n;
+    let eps = T::epsilon();
+    let (zero, one) = (T::zero(), T::one());
+
+    if norm.is_zero() {
+        return v;
+    }
+
+    for n in (0..nn).rev() {
+        let p = re[n];
+        let q = im[n];
+
+        if q.is_zero() {
+            // Real vector
+            let mut l = n;
+            let (mut z, mut s) = (zero, zero);
+            h[(n, n)] = one;
+
+            for i in (0..n).rev() {
+                let w = h[(i, i)] - p;
+                let r = (l..=n).fold(zero, |acc, j| acc + h[(i, j)] * h[(j, n)]);
+
+                if im[i] < zero {
+                    z = w;
+                    s = r;
+                    continue;
+                }
+
+                l = i;
+                if im[i].is_zero() {
+                    h[(i, n)] = if w.is_zero() {
+                        -r / (eps * norm)
+                    } else {
+                        -r / w
+                    };
+                } else {
+                    let x = h[(i, i + 1)];
+                    let y = h[(i + 1, i)];
+                    let q = (re[i] - p) * (re[i] - p) + im[i] * im[i];
+                    let t = (x * s - z * r) / q;
+                    h[(i, n)] = t;
+                    h[(i + 1, n)] = if x.abs() > z.abs() {
+                        (-r - w * t) / x
+                    } else {
+                        (-s - y * t) / z
+                    };
+                }
+
+                // Overflow control
+                let t = h[(i, n)].abs();
+                if eps * t * t > one {
+                    for j in i..=n {
+                        h[(j, n)] = h[(j, n)] / t;
+                    }
+                }
+            }
+        } else if q < zero {
+            // Complex vector, stored in columns `n - 1` (real part) and `n` (imaginary part)
+            let mut l = n - 1;
+            let (mut z, mut r, mut s) = (zero, zero, zero);
+
+            // The last component is imaginary, so the block is triangular
+            let last = if h[(n, n - 1)].abs() > h[(n - 1, n)].abs() {
+                Complex::new(q / h[(n, n - 1)], -(h[(n, n)] - p) / h[(n, n - 1)])
+            } else {
+                Complex::new(zero, -h[(n - 1, n)]) / Complex::new(h[(n - 1, n - 1)] - p, q)
+            };
+            h[(n - 1, n - 1)] = last.re;
+            h[(n - 1, n)] = last.im;
+            h[(n, n - 1)] = zero;
+            h[(n, n)] = one;
+
+            for i in (0..n - 1).rev() {
+                let (ra, sa) = (l..=n).fold((zero, zero), |(ra, sa), j| {
+                    (ra + h[(i, j)] * h[(j, n - 1)], sa + h[(i, j)] * h[(j, n)])
+                });
+                let w = h[(i, i)] - p;
+
+                if im[i] < zero {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                    continue;
+                }
+
+                l = i;
+                if im[i].is_zero() {
+                    let x = Complex::new(-ra, -sa) / Complex::new(w, q);
+                    h[(i, n - 1)] = x.re;
+                    h[(i, n)] = x.im;
+                } else {
+                    // Solve the complex equations of a 2 * 2 block
+                    let x = h[(i, i + 1)];
+                    let y = h[(i + 1, i)];
+                    let two = one + one;
+                    let mut vr = (re[i] - p) * (re[i] - p) + im[i] * im[i] - q * q;
+                    let vi = (re[i] - p) * two * q;
+                    if vr.is_zero() && vi.is_zero() {
+                        vr = eps * norm * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                    }
+
+                    let first = Complex::new(x * r - z * ra + q * sa, x * s - z * sa - q * ra)
+                        / Complex::new(vr, vi);
+                    h[(i, n - 1)] = first.re;
+                    h[(i, n)] = first.im;
+
+                    if x.abs() > z.abs() + q.abs() {
+                        h[(i + 1, n - 1)] = (-ra - w * first.re + q * first.im) / x;
+                        h[(i + 1, n)] = (-sa - w * first.im - q * first.re) / x;
+                    } else {
+                        let second =
+                            Complex::new(-r - y * first.re, -s - y * first.im) / Complex::new(z, q);
+                        h[(i + 1, n - 1)] = second.re;
+                        h[(i + 1, n)] = second.im;
+                    }
+                }
+
+                // Overflow control
+                let t = h[(i, n - 1)].abs().max(h[(i, n)].abs());
+                if eps * t * t > one {
+                    for j in i..=n {
+                        h[(j, n - 1)] = h[(j, n - 1)] / t;
+                        h[(j, n)] = h[(j, n)] / t;
+                    }
+                }
+            }
+        }
+    }
+
+    // Multiply by the Schur vectors to get the eigenvectors of the original matrix
+    for j in (0..nn).rev() {
+        for i in 0..nn {
+            v[(i, j)] = (0..=j).fold(zero, |acc, k| acc + v[(i, k)] * h[(k, j)]);
+        }
+    }
+
+    v
+}
+
+#[cfg(test)]
+mod eigen_tests {
+    use crate::linalg::test_util::assert_close_values;
+    use crate::{Matrix, MatrixError};
+
+    use num_complex::Complex;
+
+    fn sorted(mut values: Vec<Complex<f64>>) -> Vec<Complex<f64>> {
+        values.sort_by(|a, b| {
+            (a.re, a.im)
+                .partial_cmp(&(b.re, b.im))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        values
+    }
+
+    // Checks `Av = λv` and `|v| = 1` for every eigenpair
+    fn assert_eigenpairs(a: &Matrix<f64>) {
+        let n = a.size().0;
+        let eigen = a.eigen().unwrap();
+        let vectors = eigen.eigenvectors();
+
+        for (j, &value) in eigen.eigenvalues().iter().enumerate() {
+            let mut length = 0.0;
+            for i in 0..n {
+                let av = (0..n).fold(Complex::new(0.0, 0.0), |acc, k| {
+                    acc + vectors[(k, j)] * a[(i, k)]
+                });
+                assert!((av - vectors[(i, j)] * value).norm() < 1e-9);
+                length += vectors[(i, j)].norm_sqr();
+            }
+            assert!((length - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        // The companion matrix of `(x - 1)(x - 2)(x² + 2x + 5)`, with roots `1, 2, -1 ± 2i`
+        let companion = Matrix::from_vec(
+            4,
+            vec![
+                0.0, 0.0, 0.0, -10.0, 1.0, 0.0, 0.0, 11.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 1.0, 1.0,
+            ],
+        );
+
+        assert_close_values(
+            &sorted(companion.eigenvalues().unwrap()),
+            &[
+                Complex::new(-1.0, -2.0),
+                Complex::new(-1.0, 2.0),
+                Complex::new(1.0, 0.0),
+                Complex::new(2.0, 0.0),
+            ],
+        );
+        assert_close_values(
+            &sorted(companion.schur().unwrap().eigenvalues()),
+            &sorted(companion.eigenvalues().unwrap()),
+        );
+
+        // Conjugate pairs are listed with the positive imaginary part first
+        let values = companion.eigenvalues().unwrap();
+        for pair in values.windows(2).filter(|pair| pair[0].im > 0.0) {
+            assert_eq!(pair[1], pair[0].conj());
+        }
+    }
+
+    #[test]
+    fn test_eigenvectors() {
+        let general = Matrix::from_vec(
+            4,
+            vec![
+                4.0, -1.0, 2.0, 3.0, 1.0, 0.0, -2.0, 5.0, 3.0, 1.0, 1.0, -4.0, 0.5, 2.0, -1.0, 2.0,
+            ],
+        );
+        let rotation = Matrix::from_vec(3, vec![0.0, -1.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 2.0]);
+        let triangular = Matrix::from_vec(3, vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 0.0, 0.0, 6.0]);
+
+        assert_eigenpairs(&general);
+        assert_eigenpairs(&rotation);
+        assert_eigenpairs(&triangular);
+        assert_eigenpairs(&Matrix::zeros(3));
+    }
+
+    #[test]
+    fn test_failures() {
+        assert_eq!(
+            Matrix::<f64>::zeros((3, 2)).eigenvalues(),
+            Err(MatrixError::NotSquare)
+        );
+        assert_eq!(Matrix::<f64>::zeros(0).eigenvalues(), Ok(vec![]));
+    }
+}
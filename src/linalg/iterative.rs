@@ -0,0 +1,1021 @@
+use super::is_negligible;
+use crate::{CsrMatrix, LinearOperator, MatrixError};
+
+use num_traits::Float;
+
+/// The preconditioner applied by the Krylov solvers, built from the entries of the operator
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Preconditioner {
+    /// No preconditioning
+    None,
+
+    /// Divide by the diagonal of the matrix
+    Jacobi,
+
+    /// The incomplete LU factorization with no fill in, which keeps the sparsity pattern of the
+    /// matrix
+    Ilu0,
+}
+
+/// The settings of an iterative solver. Iteration stops once the residual `b - Ax` is at most
+/// `tolerance` times `b` in the 2-norm, or after `max_iterations` iterations.
+/// ```
+/// use mtrs::{CsrMatrix, Matrix, Preconditioner, SolverOptions};
+///
+/// let options = SolverOptions::new()
+///     .tolerance(1e-12)
+///     .max_iterations(500)
+///     .preconditioner(Preconditioner::Jacobi);
+///
+/// let a = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![2.0, 1.0, 1.0, 2.0]));
+/// assert!(a.cg(&[3.0, 3.0], &options).unwrap().converged());
+/// ```
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SolverOptions<T> {
+    /// The residual to reach, relative to the right hand side
+    pub(super) tolerance: T,
+
+    /// The number of iterations after which to give up
+    pub(super) max_iterations: usize,
+
+    /// The preconditioner of the Krylov solvers
+    preconditioner: Preconditioner,
+
+    /// The number of GMRES iterations between restarts
+    restart: usize,
+}
+
+impl<T: Float> SolverOptions<T> {
+    /// The default settings: a relative tolerance of the square root of the machine epsilon
+    /// (about `1.5e-8` for `f64`), 1000 iterations, no preconditioner, and restarting GMRES
+    /// every 30 iterations
+    pub fn new() -> Self {
+        SolverOptions {
+            tolerance: T::epsilon().sqrt(),
+            max_iterations: 1000,
+            preconditioner: Preconditioner::None,
+            restart: 30,
+        }
+    }
+
+    /// Set the residual to reach, relative to the right hand side
+    pub fn tolerance(mut self, tolerance: T) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Set the number of iterations after which to give up
+    pub fn max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Set the preconditioner of the Krylov solvers. The stationary methods (Jacobi,
+    /// Gauss-Seidel and SOR) ignore it.
+    pub fn preconditioner(mut self, preconditioner: Preconditioner) -> Self {
+        self.preconditioner = preconditioner;
+        self
+    }
+
+    /// Set the number of GMRES iterations between restarts, at least `1`
+    pub fn restart(mut self, restart: usize) -> Self {
+        self.restart = restart.max(1);
+        self
+    }
+}
+
+impl<T: Float> Default for SolverOptions<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The outcome of an iterative solver: the last iterate, whether it reached the tolerance, and
+/// the residuals along the way
+#[derive(Clone, PartialEq, Debug)]
+pub struct IterativeSolution<T> {
+    /// The last iterate
+    solution: Vec<T>,
+
+    /// The number of iterations taken
+    iterations: usize,
+
+    /// The 2-norm of the residual before the first iteration and after each one
+    residuals: Vec<T>,
+
+    /// Whether the residual reached the tolerance
+    converged: bool,
+}
+
+impl<T: Float> IterativeSolution<T> {
+    /// The last iterate, which solves the system to the tolerance if `converged` is true
+    pub fn solution(&self) -> &[T] {
+        &self.solution
+    }
+
+    /// Take the last iterate, see `IterativeSolution::solution`
+    pub fn into_solution(self) -> Vec<T> {
+        self.solution
+    }
+
+    /// The number of iterations taken
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// The 2-norm of the residual `b - Ax` before the first iteration and after each one. Within
+    /// a restart cycle GMRES reports the estimate from its least squares problem, which equals
+    /// the true residual in exact arithmetic, and the true residual at the end of the cycle.
+    pub fn residuals(&self) -> &[T] {
+        &self.residuals
+    }
+
+    /// Whether the residual reached the tolerance within the iteration limit
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+/// Solve `Ax = b` with the conjugate gradient method, for a symmetric positive definite operator
+/// `A`, which is only ever applied to vectors. The Jacobi and ILU(0) preconditioners are built
+/// from the entries of `A`, see `LinearOperator::as_csr`.
+/// ```
+/// use mtrs::{cg, FnOperator, SolverOptions};
+///
+/// // The diagonal matrix with entries 1 to 4, as a closure
+/// let a = FnOperator::new((4, 4), |x: &[f64], y: &mut [f64]| {
+///     for (i, (y, x)) in y.iter_mut().zip(x).enumerate() {
+///         *y = (i + 1) as f64 * x;
+///     }
+/// });
+/// let result = cg(&a, &[1.0, 2.0, 3.0, 4.0], &SolverOptions::new()).unwrap();
+///
+/// assert!(result.converged());
+/// for x in result.solution() {
+///     assert!((x - 1.0).abs() < 1e-6);
+/// }
+/// ```
+/// # Failure
+/// Fails with `MatrixError::NotSquare` if the operator is not square,
+/// `MatrixError::DimensionMismatch` if `b` does not have one entry per row,
+/// `MatrixError::Unsupported` if a preconditioner is requested from an operator without entries,
+/// `MatrixError::Singular` if the preconditioner needs a zero diagonal entry or pivot, and
+/// `MatrixError::NonPositiveCurvature` (at the iteration where it was detected) if a search
+/// direction has a non-positive curvature
+pub fn cg<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    b: &[T],
+    options: &SolverOptions<T>,
+) -> Result<IterativeSolution<T>, MatrixError> {
+    let preconditioner = prepare(a, b, options.preconditioner)?;
+    let n = b.len();
+    let mut history = History::new(norm(b), options);
+
+    let mut x = vec![T::zero(); n];
+    let mut r = b.to_vec();
+    let mut z = vec![T::zero(); n];
+    let mut q = vec![T::zero(); n];
+    preconditioner.apply(&r, &mut z);
+    let mut p = z.clone();
+    let mut rz = dot(&r, &z);
+
+    while !history.is_done() {
+        a.apply(&p, &mut q);
+        let curvature = dot(&p, &q);
+        if curvature <= T::zero() {
+            return Err(MatrixError::NonPositiveCurvature {
+                iteration: history.iterations,
+            });
+        }
+
+        let alpha = rz / curvature;
+        axpy(alpha, &p, &mut x);
+        axpy(-alpha, &q, &mut r);
+        history.push(norm(&r));
+
+        preconditioner.apply(&r, &mut z);
+        let rz_next = dot(&r, &z);
+        let beta = rz_next / rz;
+        rz = rz_next;
+        for (p, &z) in p.iter_mut().zip(z.iter()) {
+            *p = z + beta * *p;
+        }
+    }
+
+    Ok(history.finish(x))
+}
+
+/// Solve `Ax = b` with the stabilized biconjugate gradient method (BiCGSTAB), for a general
+/// square operator `A`. The preconditioner is applied on the right, so the residuals are those of
+/// the original system.
+/// ```
+/// use mtrs::{bicgstab, Matrix, SolverOptions};
+///
+/// let a = Matrix::from_vec(3, vec![4.0_f64, -1.0, 0.0, 2.0, 5.0, -2.0, 0.0, 1.0, 3.0]);
+/// let result = bicgstab(&a, &[3.0, 5.0, 4.0], &SolverOptions::new()).unwrap();
+///
+/// assert!(result.converged());
+/// ```
+/// # Failure
+/// Fails with `MatrixError::NotSquare` if the operator is not square,
+/// `MatrixError::DimensionMismatch` if `b` does not have one entry per row,
+/// `MatrixError::Unsupported` if a preconditioner is requested from an operator without entries,
+/// and `MatrixError::Singular` if the preconditioner needs a zero diagonal entry or pivot. A
+/// breakdown of the method stops it early, without converging.
+pub fn bicgstab<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    b: &[T],
+    options: &SolverOptions<T>,
+) -> Result<IterativeSolution<T>, MatrixError> {
+    let preconditioner = prepare(a, b, options.preconditioner)?;
+    let n = b.len();
+    let mut history = History::new(norm(b), options);
+
+    let mut x = vec![T::zero(); n];
+    let mut r = b.to_vec();
+    let shadow = b.to_vec();
+    let (mut p, mut v) = (vec![T::zero(); n], vec![T::zero(); n]);
+    let (mut p_hat, mut s_hat) = (vec![T::zero(); n], vec![T::zero(); n]);
+    let mut t = vec![T::zero(); n];
+    let (mut rho, mut alpha, mut omega) = (T::one(), T::one(), T::one());
+
+    while !history.is_done() {
+        let rho_next = dot(&shadow, &r);
+        if rho_next.is_zero() || omega.is_zero() {
+            break;
+        }
+
+        let beta = (rho_next / rho) * (alpha / omega);
+        rho = rho_next;
+        for ((p, &r), &v) in p.iter_mut().zip(r.iter()).zip(v.iter()) {
+            *p = r + beta * (*p - omega * v);
+        }
+
+        preconditioner.apply(&p, &mut p_hat);
+        a.apply(&p_hat, &mut v);
+        let shadow_v = dot(&shadow, &v);
+        if shadow_v.is_zero() {
+            break;
+        }
+
+        alpha = rho / shadow_v;
+        axpy(alpha, &p_hat, &mut x);
+        // `r` becomes the intermediate residual `s`
+        axpy(-alpha, &v, &mut r);
+        let s_norm = norm(&r);
+        if history.reaches(s_norm) {
+            history.push(s_norm);
+            break;
+        }
+
+        preconditioner.apply(&r, &mut s_hat);
+        a.apply(&s_hat, &mut t);
+        let tt = dot(&t, &t);
+        omega = if tt.is_zero() {
+            T::zero()
+        } else {
+            dot(&t, &r) / tt
+        };
+
+        axpy(omega, &s_hat, &mut x);
+        axpy(-omega, &t, &mut r);
+        history.push(norm(&r));
+    }
+
+    Ok(history.finish(x))
+}
+
+/// Solve `Ax = b` with the restarted generalized minimal residual method (GMRES), for a general
+/// square operator `A`. The Krylov basis is rebuilt every `restart` iterations (see
+/// `SolverOptions::restart`), which bounds the memory used to `restart` vectors. The
+/// preconditioner is applied on the right. A breakdown on a singular operator stops it early,
+/// without converging, at the least squares solution over the Krylov space built so far.
+/// ```
+/// use mtrs::{gmres, Matrix, SolverOptions};
+///
+/// let a = Matrix::from_vec(3, vec![4.0_f64, -1.0, 0.0, 2.0, 5.0, -2.0, 0.0, 1.0, 3.0]);
+/// let result = gmres(&a.as_view(), &[3.0, 5.0, 4.0], &SolverOptions::new()).unwrap();
+///
+/// assert!(result.converged());
+/// assert_eq!(result.iterations(), 3);
+/// ```
+/// # Failure
+/// Fails with `MatrixError::NotSquare` if the operator is not square,
+/// `MatrixError::DimensionMismatch` if `b` does not have one entry per row,
+/// `MatrixError::Unsupported` if a preconditioner is requested from an operator without entries,
+/// and `MatrixError::Singular` if the preconditioner needs a zero diagonal entry or pivot
+pub fn gmres<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    b: &[T],
+    options: &SolverOptions<T>,
+) -> Result<IterativeSolution<T>, MatrixError> {
+    let preconditioner = prepare(a, b, options.preconditioner)?;
+    let (n, restart) = (b.len(), options.restart.max(1));
+    let mut history = History::new(norm(b), options);
+
+    let mut x = vec![T::zero(); n];
+    let mut r = b.to_vec();
+    let (mut z, mut w) = (vec![T::zero(); n], vec![T::zero(); n]);
+    let mut breakdown = false;
+
+    while !history.is_done() && !breakdown {
+        let beta = norm(&r);
+        let mut basis = vec![r.iter().map(|&r| r / beta).collect::<Vec<T>>()];
+        // The columns of the Hessenberg matrix, reduced to upper triangular form by Givens
+        // rotations as they are added
+        let mut columns: Vec<Vec<T>> = Vec::with_capacity(restart);
+        let mut rotations: Vec<(T, T)> = Vec::with_capacity(restart);
+        let mut g = vec![beta];
+
+        while columns.len() < restart && !history.is_done() {
+            let j = columns.len();
+            preconditioner.apply(&basis[j], &mut z);
+            a.apply(&z, &mut w);
+
+            // Arnoldi step with modified Gram-Schmidt
+            let mut column = Vec::with_capacity(j + 2);
+            for vector in &basis {
+                let h = dot(&w, vector);
+                axpy(-h, vector, &mut w);
+                column.push(h);
+            }
+            let next = norm(&w);
+            column.push(next);
+            // Rotations keep the length of the column
+            let length = norm(&column);
+
+            for (i, &(c, s)) in rotations.iter().enumerate() {
+                let (upper, lower) = (column[i], column[i + 1]);
+                column[i] = c * upper + s * lower;
+                column[i + 1] = c * lower - s * upper;
+            }
+
+            let (upper, lower) = (column[j], column[j + 1]);
+            let radius = upper.hypot(lower);
+            if is_negligible(radius, T::epsilon() * length) {
+                // The new direction adds nothing to the span of the previous ones, so the least
+                // squares problem is singular. Keep the solution so far, which restarting would
+                // not improve.
+                breakdown = true;
+                break;
+            }
+
+            let (c, s) = (upper / radius, lower / radius);
+            column[j] = radius;
+            column.pop();
+            rotations.push((c, s));
+            g.push(-s * g[j]);
+            g[j] = c * g[j];
+
+            columns.push(column);
+            history.push(g[j + 1].abs());
+            if next.is_zero() {
+                // The Krylov space is invariant, so the least squares solution is exact
+                break;
+            }
+            basis.push(w.iter().map(|&w| w / next).collect());
+        }
+
+        // Back substitution for the coefficients of the basis, then `x += M⁻¹ V y`
+        let k = columns.len();
+        let mut y = g[..k].to_vec();
+        for i in (0..k).rev() {
+            for j in (i + 1)..k {
+                y[i] = y[i] - columns[j][i] * y[j];
+            }
+            y[i] = y[i] / columns[i][i];
+        }
+
+        w.iter_mut().for_each(|w| *w = T::zero());
+        for (vector, &coefficient) in basis.iter().zip(y.iter()) {
+            axpy(coefficient, vector, &mut w);
+        }
+        preconditioner.apply(&w, &mut z);
+        axpy(T::one(), &z, &mut x);
+
+        // Convergence is judged on the true residual rather than the estimate
+        let true_residual = residual(a, b, &x, &mut r);
+        history.correct(true_residual);
+    }
+
+    Ok(history.finish(x))
+}
+
+impl<T: Float> CsrMatrix<T> {
+    /// Solve `Ax = b` with the conjugate gradient method, for symmetric positive definite `A`,
+    /// see `cg`
+    /// ```
+    /// use mtrs::{CsrMatrix, Matrix, Preconditioner, SolverOptions};
+    ///
+    /// let dense = Matrix::from_vec(3, vec![4.0_f64, 1.0, 0.0, 1.0, 3.0, 1.0, 0.0, 1.0, 2.0]);
+    /// let a = CsrMatrix::from_dense(&dense);
+    /// let options = SolverOptions::new().preconditioner(Preconditioner::Jacobi);
+    /// let result = a.cg(&[5.0, 5.0, 3.0], &options).unwrap();
+    ///
+    /// assert!(result.converged());
+    /// assert!(result.iterations() <= 3);
+    /// for x in result.solution() {
+    ///     assert!((x - 1.0).abs() < 1e-6);
+    /// }
+    /// ```
+    /// # Failure
+    /// Fails under the same conditions as `cg`
+    pub fn cg(
+        &self,
+        b: &[T],
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        cg(self, b, options)
+    }
+
+    /// Solve `Ax = b` with the stabilized biconjugate gradient method, for general square `A`,
+    /// see `bicgstab`
+    /// ```
+    /// use mtrs::{CsrMatrix, Matrix, Preconditioner, SolverOptions};
+    ///
+    /// let dense = Matrix::from_vec(3, vec![4.0_f64, -1.0, 0.0, 2.0, 5.0, -2.0, 0.0, 1.0, 3.0]);
+    /// let a = CsrMatrix::from_dense(&dense);
+    /// let options = SolverOptions::new().preconditioner(Preconditioner::Ilu0);
+    /// let result = a.bicgstab(&[3.0, 5.0, 4.0], &options).unwrap();
+    ///
+    /// assert!(result.converged());
+    /// for x in result.solution() {
+    ///     assert!((x - 1.0).abs() < 1e-6);
+    /// }
+    /// ```
+    /// # Failure
+    /// Fails under the same conditions as `bicgstab`
+    pub fn bicgstab(
+        &self,
+        b: &[T],
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        bicgstab(self, b, options)
+    }
+
+    /// Solve `Ax = b` with the restarted generalized minimal residual method, for general square
+    /// `A`, see `gmres`
+    /// ```
+    /// use mtrs::{CsrMatrix, Matrix, SolverOptions};
+    ///
+    /// let dense = Matrix::from_vec(3, vec![4.0_f64, -1.0, 0.0, 2.0, 5.0, -2.0, 0.0, 1.0, 3.0]);
+    /// let a = CsrMatrix::from_dense(&dense);
+    /// let result = a.gmres(&[3.0, 5.0, 4.0], &SolverOptions::new()).unwrap();
+    ///
+    /// assert!(result.converged());
+    /// assert_eq!(result.iterations(), 3);
+    /// ```
+    /// # Failure
+    /// Fails under the same conditions as `gmres`
+    pub fn gmres(
+        &self,
+        b: &[T],
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        gmres(self, b, options)
+    }
+
+    /// Solve `Ax = b` with Jacobi iteration, which updates every entry of `x` from the previous
+    /// iterate. It converges for strictly diagonally dominant `A`, and is mostly useful as a
+    /// smoother.
+    /// ```
+    /// use mtrs::{CsrMatrix, Matrix, SolverOptions};
+    ///
+    /// let a = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![4.0_f64, 1.0, 2.0, 5.0]));
+    /// let result = a.jacobi(&[5.0, 7.0], &SolverOptions::new()).unwrap();
+    ///
+    /// assert!(result.converged());
+    /// assert!((result.solution()[1] - 1.0).abs() < 1e-6);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square,
+    /// `MatrixError::DimensionMismatch` if `b` does not have one entry per row, and
+    /// `MatrixError::Singular` if a diagonal entry is zero
+    pub fn jacobi(
+        &self,
+        b: &[T],
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        check_system(self, b)?;
+        let diagonal = self.diagonal()?;
+        let n = b.len();
+        let mut history = History::new(norm(b), options);
+
+        let (mut x, mut next) = (vec![T::zero(); n], vec![T::zero(); n]);
+        let mut r = vec![T::zero(); n];
+        while !history.is_done() {
+            for (row, entry) in next.iter_mut().enumerate() {
+                *entry = (b[row] - self.off_diagonal_dot(row, &x)) / diagonal[row];
+            }
+            std::mem::swap(&mut x, &mut next);
+            history.push(residual(self, b, &x, &mut r));
+        }
+
+        Ok(history.finish(x))
+    }
+
+    /// Solve `Ax = b` with Gauss-Seidel iteration, which updates the entries of `x` in place one
+    /// row at a time. It converges for strictly diagonally dominant or symmetric positive
+    /// definite `A`. This is successive over-relaxation with a factor of `1`, see `CsrMatrix::sor`.
+    /// # Failure
+    /// Fails under the same conditions as `CsrMatrix::jacobi`
+    pub fn gauss_seidel(
+        &self,
+        b: &[T],
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        self.sor(b, T::one(), options)
+    }
+
+    /// Solve `Ax = b` with successive over-relaxation (SOR), which moves each entry of `x` a
+    /// factor `omega` of the way to its Gauss-Seidel update. For symmetric positive definite `A`
+    /// it converges for any `omega` strictly between `0` and `2`.
+    /// ```
+    /// use mtrs::{CsrMatrix, Matrix, SolverOptions};
+    ///
+    /// let a = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![4.0_f64, 1.0, 1.0, 3.0]));
+    /// let result = a.sor(&[5.0, 4.0], 1.1, &SolverOptions::new()).unwrap();
+    ///
+    /// assert!(result.converged());
+    /// assert!((result.solution()[0] - 1.0).abs() < 1e-6);
+    /// ```
+    /// # Failure
+    /// Fails under the same conditions as `CsrMatrix::jacobi`
+    pub fn sor(
+        &self,
+        b: &[T],
+        omega: T,
+        options: &SolverOptions<T>,
+    ) -> Result<IterativeSolution<T>, MatrixError> {
+        check_system(self, b)?;
+        let diagonal = self.diagonal()?;
+        let n = b.len();
+        let mut history = History::new(norm(b), options);
+
+        let mut x = vec![T::zero(); n];
+        let mut r = vec![T::zero(); n];
+        while !history.is_done() {
+            for row in 0..n {
+                let update = (b[row] - self.off_diagonal_dot(row, &x)) / diagonal[row];
+                x[row] = x[row] + omega * (update - x[row]);
+            }
+            history.push(residual(self, b, &x, &mut r));
+        }
+
+        Ok(history.finish(x))
+    }
+
+    // The diagonal entries of a square matrix, none of which may be zero
+    fn diagonal(&self) -> Result<Vec<T>, MatrixError> {
+        (0..self.size().0)
+            .map(|row| match self.get((row, row)) {
+                Some(entry) if !entry.is_zero() => Ok(entry),
+                _ => Err(MatrixError::Singular),
+            })
+            .collect()
+    }
+
+    // The dot product of a row, without its diagonal entry, with `x`
+    fn off_diagonal_dot(&self, row: usize, x: &[T]) -> T {
+        let (indptr, indices, values) = (self.indptr(), self.indices(), self.values());
+        (indptr[row]..indptr[row + 1])
+            .filter(|&position| indices[position] != row)
+            .fold(T::zero(), |acc, position| {
+                acc + values[position] * x[indices[position]]
+            })
+    }
+}
+
+// Checks that `Ax = b` is a square system
+fn check_system<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    b: &[T],
+) -> Result<(), MatrixError> {
+    let (height, width) = a.shape();
+    if height != width {
+        return Err(MatrixError::NotSquare);
+    }
+
+    if b.len() != height {
+        return Err(MatrixError::DimensionMismatch {
+            left: a.shape(),
+            right: (b.len(), 1),
+        });
+    }
+
+    Ok(())
+}
+
+// Checks the system and sets up the preconditioner from the entries of the operator
+fn prepare<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    b: &[T],
+    kind: Preconditioner,
+) -> Result<Prepared<T>, MatrixError> {
+    check_system(a, b)?;
+    let entries = || a.as_csr().ok_or(MatrixError::Unsupported);
+    Ok(match kind {
+        Preconditioner::None => Prepared::Identity,
+        Preconditioner::Jacobi => Prepared::Jacobi(entries()?.diagonal()?),
+        Preconditioner::Ilu0 => Prepared::Ilu0(Ilu0::new(&*entries()?)?),
+    })
+}
+
+// Tracks the residuals of a solver against its tolerance and iteration limit
+struct History<T> {
+    threshold: T,
+    max_iterations: usize,
+    iterations: usize,
+    residuals: Vec<T>,
+}
+
+impl<T: Float> History<T> {
+    fn new(initial: T, options: &SolverOptions<T>) -> Self {
+        History {
+            threshold: options.tolerance * initial,
+            max_iterations: options.max_iterations,
+            iterations: 0,
+            residuals: vec![initial],
+        }
+    }
+
+    // Records the residual after another iteration
+    fn push(&mut self, residual: T) {
+        self.iterations += 1;
+        self.residuals.push(residual);
+    }
+
+    // Replaces the last residual with the true one, where it was only estimated
+    fn correct(&mut self, residual: T) {
+        *self.residuals.last_mut().unwrap() = residual;
+    }
+
+    // Whether a residual is within the tolerance
+    fn reaches(&self, residual: T) -> bool {
+        residual <= self.threshold
+    }
+
+    // Whether the last residual is within the tolerance (or not a number), or the iterations
+    // have run out
+    fn is_done(&self) -> bool {
+        let last = *self.residuals.last().unwrap();
+        self.reaches(last) || last.is_nan() || self.iterations >= self.max_iterations
+    }
+
+    fn finish(self, solution: Vec<T>) -> IterativeSolution<T> {
+        let converged = self.reaches(*self.residuals.last().unwrap());
+        IterativeSolution {
+            solution,
+            iterations: self.iterations,
+            residuals: self.residuals,
+            converged,
+        }
+    }
+}
+
+// A preconditioner `M`, ready to apply `M⁻¹`
+enum Prepared<T> {
+    Identity,
+    Jacobi(Vec<T>),
+    Ilu0(Ilu0<T>),
+}
+
+impl<T: Float> Prepared<T> {
+    // Sets `z = M⁻¹ r`
+    fn apply(&self, r: &[T], z: &mut [T]) {
+        match self {
+            Prepared::Identity => z.copy_from_slice(r),
+            Prepared::Jacobi(diagonal) => {
+                for ((z, &r), &d) in z.iter_mut().zip(r.iter()).zip(diagonal.iter()) {
+                    *z = r / d;
+                }
+            }
+            Prepared::Ilu0(factors) => factors.solve(r, z),
+        }
+    }
+}
+
+// The incomplete LU factorization of a CSR matrix without fill in. `values` holds the entries of
+// the unit lower triangular `L` below the diagonal and of `U` on and above it, in the sparsity
+// pattern of the matrix.
+struct Ilu0<T> {
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<T>,
+    diagonal: Vec<usize>,
+}
+
+impl<T: Float> Ilu0<T> {
+    fn new(matrix: &CsrMatrix<T>) -> Result<Self, MatrixError> {
+        let (indptr, indices) = (matrix.indptr().to_vec(), matrix.indices().to_vec());
+        let mut values = matrix.values().to_vec();
+        let n = matrix.size().0;
+        let tolerance = T::epsilon() * values.iter().fold(T::zero(), |acc, x| acc.max(x.abs()));
+
+        let mut diagonal = Vec::with_capacity(n);
+        for row in 0..n {
+            let along = &indices[indptr[row]..indptr[row + 1]];
+            let offset = along
+                .binary_search(&row)
+                .map_err(|_| MatrixError::Singular)?;
+            diagonal.push(indptr[row] + offset);
+        }
+
+        for row in 0..n {
+            let end = indptr[row + 1];
+            for position in indptr[row]..diagonal[row] {
+                // Eliminate the entry of `L` at `(row, k)` with row `k` of `U`
+                let k = indices[position];
+                let factor = values[position] / values[diagonal[k]];
+                values[position] = factor;
+
+                let mut target = position + 1;
+                for source in (diagonal[k] + 1)..indptr[k + 1] {
+                    let col = indices[source];
+                    while target < end && indices[target] < col {
+                        target += 1;
+                    }
+                    if target < end && indices[target] == col {
+                        values[target] = values[target] - factor * values[source];
+                    }
+                }
+            }
+
+            if is_negligible(values[diagonal[row]], tolerance) {
+                return Err(MatrixError::Singular);
+            }
+        }
+
+        Ok(Ilu0 {
+            indptr,
+            indices,
+            values,
+            diagonal,
+        })
+    }
+
+    // Sets `z = (LU)⁻¹ r` by forward and back substitution
+    fn solve(&self, r: &[T], z: &mut [T]) {
+        let n = r.len();
+        for row in 0..n {
+            let mut sum = r[row];
+            for position in self.indptr[row]..self.diagonal[row] {
+                sum = sum - self.values[position] * z[self.indices[position]];
+            }
+            z[row] = sum;
+        }
+
+        for row in (0..n).rev() {
+            let mut sum = z[row];
+            for position in (self.diagonal[row] + 1)..self.indptr[row + 1] {
+                sum = sum - self.values[position] * z[self.indices[position]];
+            }
+            z[row] = sum / self.values[self.diagonal[row]];
+        }
+    }
+}
+
+// Sets `r = b - Ax`, returning its 2-norm
+fn residual<T: Float, A: LinearOperator<T> + ?Sized>(a: &A, b: &[T], x: &[T], r: &mut [T]) -> T {
+    a.apply(x, r);
+    for (r, &b) in r.iter_mut().zip(b.iter()) {
+        *r = b - *r;
+    }
+    norm(r)
+}
+
+pub(super) fn dot<T: Float>(x: &[T], y: &[T]) -> T {
+    x.iter()
+        .zip(y.iter())
+        .fold(T::zero(), |acc, (&x, &y)| acc + x * y)
+}
+
+pub(super) fn norm<T: Float>(x: &[T]) -> T {
+    dot(x, x).sqrt()
+}
+
+// Sets `y = y + alpha * x`
+pub(super) fn axpy<T: Float>(alpha: T, x: &[T], y: &mut [T]) {
+    for (y, &x) in y.iter_mut().zip(x.iter()) {
+        *y = *y + alpha * x;
+    }
+}
+
+#[cfg(test)]
+mod iterative_tests {
+    use super::{cg, gmres, Preconditioner, SolverOptions};
+    use crate::{CooMatrix, CsrMatrix, FnOperator, LinearOperator, Matrix, MatrixError};
+
+    // The 5 point Laplacian on an `n * n` grid, which is symmetric positive definite
+    fn laplacian(n: usize) -> CsrMatrix<f64> {
+        let mut coo = CooMatrix::new(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let row = i * n + j;
+                coo.push((row, row), 4.0).unwrap();
+                if i > 0 {
+                    coo.push((row, row - n), -1.0).unwrap();
+                }
+                if i + 1 < n {
+                    coo.push((row, row + n), -1.0).unwrap();
+                }
+                if j > 0 {
+                    coo.push((row, row - 1), -1.0).unwrap();
+                }
+                if j + 1 < n {
+                    coo.push((row, row + 1), -1.0).unwrap();
+                }
+            }
+        }
+
+        coo.to_csr()
+    }
+
+    // The Laplacian plus an upwind convection term, which is not symmetric
+    fn convection(n: usize) -> CsrMatrix<f64> {
+        let mut coo = laplacian(n).to_coo();
+        for row in 1..n * n {
+            coo.push((row, row), 0.5).unwrap();
+            coo.push((row, row - 1), -0.5).unwrap();
+        }
+
+        coo.to_csr()
+    }
+
+    fn rhs(len: usize) -> Vec<f64> {
+        (0..len).map(|i| ((i * 7) % 5) as f64 - 1.5).collect()
+    }
+
+    // Checks that `x` solves `Ax = b` to a relative residual of `tolerance`
+    fn assert_residual(a: &CsrMatrix<f64>, x: &[f64], b: &[f64], tolerance: f64) {
+        let ax = a * &Matrix::from_slice((x.len(), 1), x);
+        let residual: f64 = ax.iter().zip(b).map(|(ax, b)| (b - ax).powi(2)).sum();
+        let scale: f64 = b.iter().map(|b| b * b).sum();
+        assert!(residual.sqrt() <= tolerance * scale.sqrt());
+    }
+
+    #[test]
+    fn test_krylov_solvers() {
+        let (spd, general) = (laplacian(12), convection(12));
+        let b = rhs(144);
+
+        for &preconditioner in &[
+            Preconditioner::None,
+            Preconditioner::Jacobi,
+            Preconditioner::Ilu0,
+        ] {
+            let options = SolverOptions::new()
+                .tolerance(1e-10)
+                .preconditioner(preconditioner);
+
+            let results = vec![
+                (&spd, spd.cg(&b, &options).unwrap()),
+                (&general, general.bicgstab(&b, &options).unwrap()),
+                (&general, general.gmres(&b, &options).unwrap()),
+                (&general, general.gmres(&b, &options.restart(5)).unwrap()),
+            ];
+
+            for (a, result) in results {
+                assert!(result.converged());
+                assert_eq!(result.residuals().len(), result.iterations() + 1);
+                assert_residual(a, result.solution(), &b, 1e-9);
+            }
+        }
+
+        // Preconditioning pays off
+        let plain = spd.cg(&b, &SolverOptions::new()).unwrap();
+        let options = SolverOptions::new().preconditioner(Preconditioner::Ilu0);
+        assert!(spd.cg(&b, &options).unwrap().iterations() < plain.iterations());
+    }
+
+    #[test]
+    fn test_operators() {
+        let a = laplacian(8);
+        let b = rhs(64);
+        let options = SolverOptions::new().tolerance(1e-10);
+        let expected = a.cg(&b, &options).unwrap();
+
+        // The same iterations whether `A` is stored or only applied
+        let closure = FnOperator::new(a.shape(), |x: &[f64], y: &mut [f64]| a.apply(x, y));
+        assert_eq!(cg(&closure, &b, &options).unwrap(), expected);
+        let dense = a.to_dense();
+        let result = cg(&dense, &b, &options).unwrap();
+        assert_eq!(result.iterations(), expected.iterations());
+        assert_residual(&a, result.solution(), &b, 1e-9);
+
+        // Preconditioners need the entries of the operator
+        let jacobi = options.preconditioner(Preconditioner::Jacobi);
+        assert_eq!(gmres(&closure, &b, &jacobi), Err(MatrixError::Unsupported));
+        assert!(gmres(&a.to_csc(), &b, &jacobi).unwrap().converged());
+    }
+
+    #[test]
+    fn test_rank_deficient_operators() {
+        // Keeps the first half of a vector and zeroes the rest
+        let projection = FnOperator::new((10, 10), |x: &[f64], y: &mut [f64]| {
+            for (i, (y, &x)) in y.iter_mut().zip(x).enumerate() {
+                *y = if i < 5 { x } else { 0.0 };
+            }
+        });
+        let ones = Matrix::from_vec(3, vec![1.0; 9]);
+        let cases: Vec<(&dyn LinearOperator<f64>, Vec<f64>)> =
+            vec![(&projection, rhs(10)), (&ones, vec![1.0, 0.0, 0.0])];
+
+        for (a, b) in cases {
+            let result = gmres(a, &b, &SolverOptions::new()).unwrap();
+            assert!(!result.converged());
+            assert!(result.solution().iter().all(|x| x.is_finite()));
+
+            // The last reported residual is the true one
+            let mut ax = vec![0.0; b.len()];
+            a.apply(result.solution(), &mut ax);
+            let true_residual: f64 = ax.iter().zip(&b).map(|(ax, b)| (b - ax).powi(2)).sum();
+            let last = *result.residuals().last().unwrap();
+            assert!((last - true_residual.sqrt()).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_stationary_solvers() {
+        let a = convection(6);
+        let b = rhs(36);
+        let options = SolverOptions::new().tolerance(1e-10).max_iterations(5000);
+
+        for result in [
+            a.jacobi(&b, &options).unwrap(),
+            a.gauss_seidel(&b, &options).unwrap(),
+            a.sor(&b, 1.5, &options).unwrap(),
+        ] {
+            assert!(result.converged());
+            assert_residual(&a, result.solution(), &b, 1e-9);
+        }
+
+        let gauss_seidel = a.gauss_seidel(&b, &options).unwrap();
+        assert!(gauss_seidel.iterations() < a.jacobi(&b, &options).unwrap().iterations());
+        assert!(a.sor(&b, 1.5, &options).unwrap().iterations() < gauss_seidel.iterations());
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let a = laplacian(10);
+        let result = a
+            .cg(&rhs(100), &SolverOptions::new().max_iterations(3))
+            .unwrap();
+
+        assert!(!result.converged());
+        assert_eq!(result.iterations(), 3);
+        assert_eq!(result.residuals().len(), 4);
+        assert!(result.residuals()[3] < result.residuals()[0]);
+
+        let zero = a.gmres(&[0.0; 100], &SolverOptions::new()).unwrap();
+        assert!(zero.converged());
+        assert_eq!(zero.iterations(), 0);
+        assert_eq!(zero.into_solution(), vec![0.0; 100]);
+    }
+
+    #[test]
+    fn test_failures() {
+        let options = SolverOptions::new();
+        let rectangular = CsrMatrix::from_dense(&Matrix::<f64>::zeros((2, 3)));
+        assert_eq!(
+            rectangular.cg(&[1.0, 1.0], &options),
+            Err(MatrixError::NotSquare)
+        );
+
+        let a = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![0.0, 1.0, 1.0, 0.0]));
+        assert_eq!(
+            a.gmres(&[1.0], &options),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 2),
+                right: (1, 1)
+            })
+        );
+        assert_eq!(a.jacobi(&[1.0, 1.0], &options), Err(MatrixError::Singular));
+        assert_eq!(
+            a.bicgstab(&[1.0, 1.0], &options.preconditioner(Preconditioner::Ilu0)),
+            Err(MatrixError::Singular)
+        );
+        // Without a preconditioner the permutation is solved exactly
+        assert!(a.gmres(&[1.0, 2.0], &options).unwrap().converged());
+
+        // A singular matrix breaks GMRES down without a false convergence
+        let singular = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![1.0, 1.0, 1.0, 1.0]));
+        let result = singular.gmres(&[1.0, 0.0], &options).unwrap();
+        assert!(!result.converged());
+        // The least squares solution over the first Krylov vector
+        assert!((result.solution()[0] - 0.5).abs() < 1e-12 && result.solution()[1] == 0.0);
+        assert!((result.residuals().last().unwrap() - 0.5_f64.sqrt()).abs() < 1e-12);
+
+        let zero = CsrMatrix::from_dense(&Matrix::<f64>::zeros(2));
+        let result = zero.gmres(&[1.0, 1.0], &options).unwrap();
+        assert!(!result.converged());
+        assert_eq!(result.iterations(), 0);
+        assert_eq!(result.solution(), &[0.0, 0.0]);
+
+        let indefinite = CsrMatrix::from_dense(&Matrix::from_vec(2, vec![1.0, 0.0, 0.0, -1.0]));
+        assert_eq!(
+            indefinite.cg(&[1.0, 1.0], &options),
+            Err(MatrixError::NonPositiveCurvature { iteration: 0 })
+        );
+    }
+}
@@ -0,0 +1,257 @@
+use super::iterative::{axpy, dot, norm};
+use super::{is_negligible, SolverOptions, SymmetricEigen};
+use crate::{LinearOperator, Matrix, MatrixError};
+
+use num_traits::Float;
+
+/// Find the eigenvalue of largest magnitude of a square operator, and a matching unit
+/// eigenvector, by power iteration. Iteration stops once `‖Av - λv‖` is at most `tolerance`
+/// times `|λ|` (see `SolverOptions`); the preconditioner and restart settings are ignored.
+///
+/// Convergence is linear in the ratio between the two largest eigenvalue magnitudes, so it fails
+/// when they are equal, as for `λ` and `-λ`.
+/// ```
+/// use mtrs::{power_iteration, Matrix, SolverOptions};
+///
+/// let a = Matrix::from_vec(2, vec![2.0_f64, 1.0, 1.0, 2.0]);
+/// let (value, vector) = power_iteration(&a, &SolverOptions::new()).unwrap();
+///
+/// assert!((value - 3.0).abs() < 1e-6);
+/// assert!((vector[0] - vector[1]).abs() < 1e-6);
+/// ```
+/// # Failure
+/// Fails with `MatrixError::NotSquare` if the operator is not square, and
+/// `MatrixError::NotConverged` if the tolerance is not reached within `max_iterations`
+pub fn power_iteration<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    options: &SolverOptions<T>,
+) -> Result<(T, Vec<T>), MatrixError> {
+    let n = square(a)?;
+    let mut v = start(n);
+    let mut w = vec![T::zero(); n];
+
+    for _ in 0..options.max_iterations {
+        a.apply(&v, &mut w);
+        // The Rayleigh quotient of the unit vector `v`
+        let value = dot(&v, &w);
+        let residual = w
+            .iter()
+            .zip(v.iter())
+            .fold(T::zero(), |acc, (&w, &v)| acc + (w - value * v).powi(2))
+            .sqrt();
+        if residual <= options.tolerance * value.abs() {
+            return Ok((value, v));
+        }
+
+        let length = norm(&w);
+        for (v, &w) in v.iter_mut().zip(w.iter()) {
+            *v = w / length;
+        }
+    }
+
+    Err(MatrixError::NotConverged)
+}
+
+/// Approximate the eigenpairs of a symmetric operator with `steps` steps of the Lanczos method,
+/// which projects it onto an orthonormal basis of the Krylov space of a fixed start vector.
+///
+/// The Ritz values and vectors (the eigenpairs of the projection) are returned in increasing
+/// order; the extreme ones converge first. The basis is fully reorthogonalized, which keeps it
+/// orthonormal at the cost of `steps` stored vectors. It stops early once the Krylov space is
+/// invariant, when the Ritz pairs are exact, so fewer than `steps` pairs may be returned. With
+/// `steps` at least the size of the operator, every eigenpair is found.
+/// ```
+/// use mtrs::{lanczos, FnOperator};
+///
+/// // The second difference operator, whose smallest eigenvalue is `2 - 2cos(π / 41)`
+/// let n = 40;
+/// let a = FnOperator::new((n, n), |x: &[f64], y: &mut [f64]| {
+///     for i in 0..n {
+///         let left = if i > 0 { x[i - 1] } else { 0.0 };
+///         let right = if i + 1 < n { x[i + 1] } else { 0.0 };
+///         y[i] = 2.0 * x[i] - left - right;
+///     }
+/// });
+/// let ritz = lanczos(&a, 20).unwrap();
+/// let smallest = ritz.eigenvalues()[0];
+///
+/// let exact = 2.0 - 2.0 * (std::f64::consts::PI / 41.0).cos();
+/// assert!((smallest - exact).abs() < 1e-4);
+/// ```
+/// # Failure
+/// Fails with `MatrixError::NotSquare` if the operator is not square, and with
+/// `MatrixError::NotConverged` if the eigendecomposition of the projected tridiagonal matrix does
+/// not converge
+pub fn lanczos<T: Float, A: LinearOperator<T> + ?Sized>(
+    a: &A,
+    steps: usize,
+) -> Result<SymmetricEigen<T>, MatrixError> {
+    let n = square(a)?;
+    let steps = steps.min(n);
+    let mut basis: Vec<Vec<T>> = Vec::with_capacity(steps);
+    let (mut alphas, mut betas) = (Vec::with_capacity(steps), Vec::with_capacity(steps));
+    let mut scale = T::zero();
+
+    let mut v = start(n);
+    let mut w = vec![T::zero(); n];
+    while basis.len() < steps {
+        a.apply(&v, &mut w);
+        let alpha = dot(&w, &v);
+        alphas.push(alpha);
+        basis.push(v);
+
+        // Gram-Schmidt against the whole basis, twice, in place of the three term recurrence
+        for _ in 0..2 {
+            for vector in &basis {
+                let h = dot(&w, vector);
+                axpy(-h, vector, &mut w);
+            }
+        }
+
+        let beta = norm(&w);
+        scale = scale.max(alpha.abs()).max(beta);
+        let tolerance = T::epsilon() * T::from(n).unwrap_or_else(T::one) * scale;
+        if basis.len() == steps || is_negligible(beta, tolerance) {
+            break;
+        }
+
+        betas.push(beta);
+        v = w.iter().map(|&w| w / beta).collect();
+    }
+
+    // The projection of the operator onto the basis is tridiagonal
+    let m = basis.len();
+    let mut tridiagonal = Matrix::diag(alphas);
+    for (i, &beta) in betas.iter().enumerate() {
+        tridiagonal.data[(i + 1) * m + i] = beta;
+        tridiagonal.data[i * m + i + 1] = beta;
+    }
+    let projected = tridiagonal.symmetric_eigen()?;
+
+    // Each Ritz vector combines the basis vectors with the entries of an eigenvector
+    let coefficients = projected.eigenvectors();
+    let mut vectors = Matrix::zeros((n, m));
+    for (j, vector) in basis.iter().enumerate() {
+        for (row, &entry) in vector.iter().enumerate() {
+            for col in 0..m {
+                let value = &mut vectors.data[row * m + col];
+                *value = *value + entry * coefficients.data[j * m + col];
+            }
+        }
+    }
+
+    Ok(SymmetricEigen::from_parts(
+        projected.eigenvalues().to_vec(),
+        vectors,
+    ))
+}
+
+// Checks that the operator is square, returning its size
+fn square<T: Float, A: LinearOperator<T> + ?Sized>(a: &A) -> Result<usize, MatrixError> {
+    let (height, width) = a.shape();
+    if height != width {
+        return Err(MatrixError::NotSquare);
+    }
+
+    Ok(height)
+}
+
+// A fixed unit start vector with uneven entries, so it is unlikely to be orthogonal to an
+// eigenvector
+fn start<T: Float>(n: usize) -> Vec<T> {
+    let v: Vec<T> = (0..n)
+        .map(|i| T::from((i * 7919) % 101 + 50).unwrap_or_else(T::one))
+        .collect();
+    let length = norm(&v);
+    v.into_iter().map(|v| v / length).collect()
+}
+
+#[cfg(test)]
+mod krylov_tests {
+    use super::{lanczos, power_iteration};
+    use crate::{CsrMatrix, FnOperator, LinearOperator, Matrix, MatrixError, SolverOptions};
+
+    // The second difference matrix, with eigenvalues `2 - 2cos(kπ / (n + 1))` for `k` in `1..=n`
+    fn second_difference(n: usize) -> CsrMatrix<f64> {
+        CsrMatrix::from_dense(
+            &Matrix::from_iter_shape(
+                n,
+                (0..n * n).map(|i| match (i / n) as i64 - (i % n) as i64 {
+                    0 => 2.0,
+                    1 | -1 => -1.0,
+                    _ => 0.0,
+                }),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn exact(n: usize, k: usize) -> f64 {
+        2.0 - 2.0 * (k as f64 * std::f64::consts::PI / (n + 1) as f64).cos()
+    }
+
+    #[test]
+    fn test_power_iteration() {
+        let a = second_difference(8);
+        let options = SolverOptions::new().tolerance(1e-10);
+        let (value, vector) = power_iteration(&a, &options).unwrap();
+
+        assert!((value - exact(8, 8)).abs() < 1e-9);
+        let mut av = vec![0.0; 8];
+        a.apply(&vector, &mut av);
+        for (av, v) in av.iter().zip(&vector) {
+            assert!((av - value * v).abs() < 1e-8);
+        }
+
+        // `1` and `-1` have the same magnitude
+        let reflection = Matrix::from_vec(2, vec![0.0, 1.0, 1.0, 0.0]);
+        assert_eq!(
+            power_iteration(&reflection, &options),
+            Err(MatrixError::NotConverged)
+        );
+        assert_eq!(
+            power_iteration(&Matrix::<f64>::zeros((2, 3)), &options),
+            Err(MatrixError::NotSquare)
+        );
+    }
+
+    #[test]
+    fn test_lanczos() {
+        let n = 30;
+        let a = second_difference(n);
+        let ritz = lanczos(&a, n).unwrap();
+        let vectors = ritz.eigenvectors();
+
+        assert_eq!(ritz.eigenvalues().len(), n);
+        for (k, &value) in ritz.eigenvalues().iter().enumerate() {
+            assert!((value - exact(n, k + 1)).abs() < 1e-10);
+
+            let vector = vectors.col(k).to_matrix().as_slice().to_vec();
+            let mut av = vec![0.0; n];
+            a.apply(&vector, &mut av);
+            for (av, v) in av.iter().zip(&vector) {
+                assert!((av - value * v).abs() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lanczos_breakdown() {
+        // Only three distinct eigenvalues, so the Krylov space stops growing after three steps
+        let diagonal = FnOperator::new((6, 6), |x: &[f64], y: &mut [f64]| {
+            for (i, (y, x)) in y.iter_mut().zip(x).enumerate() {
+                *y = (i / 2 + 1) as f64 * x;
+            }
+        });
+        let ritz = lanczos(&diagonal, 6).unwrap();
+
+        assert_eq!(ritz.eigenvalues().len(), 3);
+        for (value, expected) in ritz.eigenvalues().iter().zip(&[1.0, 2.0, 3.0]) {
+            assert!((value - expected).abs() < 1e-12);
+        }
+        assert_eq!(ritz.eigenvectors().size(), (6, 3));
+
+        let broken = FnOperator::new((3, 3), |_: &[f64], y: &mut [f64]| y.fill(f64::NAN));
+        assert_eq!(lanczos(&broken, 3), Err(MatrixError::NotConverged));
+    }
+}
@@ -0,0 +1,268 @@
+use super::triangular::{back_substitute, forward_substitute};
+use super::{is_negligible, pivot_tolerance};
+use crate::parallel::{self, Element};
+use crate::{Matrix, MatrixError};
+
+use num_traits::{Float, Num};
+
+/// The LU decomposition of a square matrix with partial pivoting, that is `PA = LU` where
+/// `P` is a permutation matrix, `L` is unit lower triangular and `U` is upper triangular.
+///
+/// Once computed, the factorization can be reused to solve any number of systems.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (3, 3); 2, 1, 1; 4, -6, 0; -2, 7, 2];
+/// let lu = a.lu().expect("Matrix is not square");
+///
+/// assert_eq!(lu.determinant(), -16.0);
+/// assert_eq!(lu.p() * a, lu.l() * lu.u());
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct LU<T: Num> {
+    /// The strictly lower triangle holds `L` (without its unit diagonal), the rest holds `U`
+    factors: Matrix<T>,
+
+    /// Row `i` of `PA` is row `perm[i]` of `A`
+    perm: Vec<usize>,
+
+    /// Whether `P` is an odd permutation
+    odd: bool,
+
+    /// Pivots at or below this magnitude are treated as zero
+    tolerance: T,
+}
+
+impl<T: Float + Element> Matrix<T> {
+    /// Compute the LU decomposition of the matrix, with partial pivoting
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let lu = matrix![f64; (2, 2); 1, 2; 4, 4].lu().unwrap();
+    ///
+    /// assert_eq!(lu.permutation(), &[1, 0]);
+    /// assert_eq!(lu.l(), matrix![f64; (2, 2); 1, 0; 0.25, 1]);
+    /// assert_eq!(lu.u(), matrix![f64; (2, 2); 4, 4; 0, 1]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square. Singular matrices can
+    /// still be factored, but `LU::solve` and `LU::inverse` will fail on them.
+    pub fn lu(&self) -> Result<LU<T>, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.height;
+        let mut a = self.data.clone();
+        let mut perm: Vec<usize> = (0..n).collect();
+        let mut odd = false;
+
+        for k in 0..n {
+            let mut pivot = k;
+            for row in (k + 1)..n {
+                if a[row * n + k].abs() > a[pivot * n + k].abs() {
+                    pivot = row;
+                }
+            }
+
+            if pivot != k {
+                for j in 0..n {
+                    a.swap(pivot * n + j, k * n + j);
+                }
+                perm.swap(pivot, k);
+                odd = !odd;
+            }
+
+            let diag = a[k * n + k];
+            if diag.is_zero() {
+                continue;
+            }
+
+            let (top, rest) = a.split_at_mut((k + 1) * n);
+            let pivot_row = &top[k * n..];
+            parallel::for_each_row(rest, n, |row| {
+                let factor = row[k] / diag;
+                row[k] = factor;
+
+                for (entry, &above) in row[k + 1..].iter_mut().zip(&pivot_row[k + 1..]) {
+                    *entry = *entry - factor * above;
+                }
+            });
+        }
+
+        Ok(LU {
+            tolerance: pivot_tolerance(&self.data, n),
+            factors: Self::from_vec(n, a),
+            perm,
+            odd,
+        })
+    }
+}
+
+impl<T: Float> LU<T> {
+    /// The unit lower triangular factor `L`
+    pub fn l(&self) -> Matrix<T> {
+        let n = self.factors.height;
+        let mut l = Matrix::identity(n);
+
+        for i in 0..n {
+            for j in 0..i {
+                l.data[i * n + j] = self.factors.data[i * n + j];
+            }
+        }
+
+        l
+    }
+
+    /// The upper triangular factor `U`
+    pub fn u(&self) -> Matrix<T> {
+        let n = self.factors.height;
+        let mut u = Matrix::zeros(n);
+
+        for i in 0..n {
+            for j in i..n {
+                u.data[i * n + j] = self.factors.data[i * n + j];
+            }
+        }
+
+        u
+    }
+
+    /// The permutation matrix `P`
+    pub fn p(&self) -> Matrix<T> {
+        let n = self.perm.len();
+        let mut p = Matrix::zeros(n);
+
+        for (i, &row) in self.perm.iter().enumerate() {
+            p.data[i * n + row] = T::one();
+        }
+
+        p
+    }
+
+    /// The row permutation, where row `i` of `PA` is row `permutation()[i]` of `A`
+    pub fn permutation(&self) -> &[usize] {
+        &self.perm
+    }
+
+    /// Whether the factored matrix is singular, or too close to singular to solve with
+    pub fn is_singular(&self) -> bool {
+        let n = self.factors.height;
+        (0..n).any(|i| is_negligible(self.factors.data[i * n + i], self.tolerance))
+    }
+
+    /// The determinant of the factored matrix
+    pub fn determinant(&self) -> T {
+        let n = self.factors.height;
+        let det = (0..n).fold(T::one(), |acc, i| acc * self.factors.data[i * n + i]);
+
+        if self.odd {
+            -det
+        } else {
+            det
+        }
+    }
+
+    /// Solve `AX = B` for `X`, where each column of `b` is a separate right hand side
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let lu = matrix![f64; (2, 2); 2, 1; 1, 3].lu().unwrap();
+    ///
+    /// assert_eq!(lu.solve(&matrix![f64; (2, 1); 3; 5]), Ok(matrix![f64; (2, 1); 0.8; 1.4]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `b` does not have as many rows as `A`,
+    /// and with `MatrixError::Singular` if `A` is singular
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let n = self.factors.height;
+        if b.height != n {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.factors.size(),
+                right: b.size(),
+            });
+        }
+
+        if self.is_singular() {
+            return Err(MatrixError::Singular);
+        }
+
+        let k = b.width;
+        let mut x = Vec::with_capacity(n * k);
+        for &row in &self.perm {
+            x.extend_from_slice(&b.data[row * k..(row + 1) * k]);
+        }
+
+        forward_substitute(&self.factors.data, n, &mut x, k, true);
+        back_substitute(&self.factors.data, n, &mut x, k, false);
+
+        Ok(Matrix::from_vec((n, k), x))
+    }
+
+    /// Solve `AX = B` for each of the given right hand sides
+    /// # Failure
+    /// Fails under the same conditions as `LU::solve`, for any of the right hand sides
+    pub fn solve_many(&self, bs: &[Matrix<T>]) -> Result<Vec<Matrix<T>>, MatrixError> {
+        bs.iter().map(|b| self.solve(b)).collect()
+    }
+
+    /// The inverse of the factored matrix
+    /// # Failure
+    /// Fails with `MatrixError::Singular` if the matrix is singular
+    pub fn inverse(&self) -> Result<Matrix<T>, MatrixError> {
+        self.solve(&Matrix::identity(self.factors.height))
+    }
+}
+
+#[cfg(test)]
+mod lu_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_factors() {
+        let a = Matrix::from_vec(3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0]);
+        let lu = a.lu().unwrap();
+
+        assert_close(&(lu.p() * a.clone()), &(lu.l() * lu.u()));
+        assert!((lu.determinant() + 3.0).abs() < 1e-10);
+        assert!(!lu.is_singular());
+    }
+
+    #[test]
+    fn test_solve() {
+        let a = Matrix::from_vec(3, vec![4.0, -2.0, 1.0, -2.0, 4.0, -2.0, 1.0, -2.0, 4.0]);
+        let lu = a.lu().unwrap();
+        let b1 = Matrix::from_vec((3, 1), vec![11.0, -16.0, 17.0]);
+        let b2 = Matrix::from_vec((3, 2), vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+        let xs = lu.solve_many(&[b1.clone(), b2.clone()]).unwrap();
+        assert_close(&(a.clone() * xs[0].clone()), &b1);
+        assert_close(&(a.clone() * xs[1].clone()), &b2);
+        assert_close(&(a * lu.inverse().unwrap()), &Matrix::identity(3));
+    }
+
+    #[test]
+    fn test_failures() {
+        let singular = Matrix::from_vec(2, vec![1.0, 2.0, 2.0, 4.0]);
+        let lu = singular.lu().unwrap();
+
+        assert!(lu.is_singular());
+        assert_eq!(lu.determinant(), 0.0);
+        assert_eq!(lu.inverse(), Err(MatrixError::Singular));
+        assert_eq!(
+            Matrix::<f64>::identity(2)
+                .lu()
+                .unwrap()
+                .solve(&Matrix::zeros((3, 1))),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 2),
+                right: (3, 1)
+            })
+        );
+        assert_eq!(
+            Matrix::<f64>::zeros((2, 3)).lu(),
+            Err(MatrixError::NotSquare)
+        );
+    }
+}
@@ -0,0 +1,39 @@
+mod cholesky;
+#[cfg(feature = "complex")]
+mod eigen;
+mod iterative;
+mod krylov;
+mod lu;
+mod qr;
+mod schur;
+mod solve;
+mod svd;
+mod symmetric_eigen;
+#[cfg(test)]
+mod test_util;
+pub(crate) mod triangular;
+
+pub use cholesky::{Cholesky, LDLT};
+#[cfg(feature = "complex")]
+pub use eigen::Eigen;
+pub use iterative::{bicgstab, cg, gmres, IterativeSolution, Preconditioner, SolverOptions};
+pub use krylov::{lanczos, power_iteration};
+pub use lu::LU;
+pub use qr::QR;
+pub use schur::Schur;
+pub use svd::SVD;
+pub use symmetric_eigen::SymmetricEigen;
+
+use num_traits::Float;
+
+// The magnitude at or below which a pivot is treated as zero, relative to the largest entry of
+// an `n * n` matrix
+pub(crate) fn pivot_tolerance<T: Float>(data: &[T], n: usize) -> T {
+    let scale = data.iter().fold(T::zero(), |acc, x| acc.max(x.abs()));
+    T::epsilon() * T::from(n).unwrap_or_else(T::one) * scale
+}
+
+// Whether a pivot is too small (or NaN) to divide by
+pub(crate) fn is_negligible<T: Float>(pivot: T, tolerance: T) -> bool {
+    pivot.abs() <= tolerance || pivot.is_nan()
+}
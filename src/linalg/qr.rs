@@ -0,0 +1,354 @@
+use super::triangular::{back_substitute, forward_substitute};
+use super::{is_negligible, pivot_tolerance};
+use crate::{Element, Layout, Matrix, MatrixError};
+
+use num_traits::{Float, Num};
+
+/// The QR decomposition of an `m * n` matrix, `A = QR`, computed with Householder reflections.
+///
+/// `Q` is orthogonal and `R` is upper triangular. Both are available in a thin form, where `Q` is
+/// `m * min(m, n)` and `R` is `min(m, n) * n`, and a full form, where `Q` is `m * m` and `R` is
+/// `m * n`.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (3, 2); 3, 0; 4, 0; 0, 2];
+/// let qr = a.qr();
+///
+/// assert_eq!(qr.r(), matrix![f64; (2, 2); -5, 0; 0, -2]);
+/// assert_eq!(qr.q().size(), (3, 2));
+/// assert_eq!(qr.q_full().size(), (3, 3));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct QR<T: Num> {
+    /// `R` on and above the diagonal, and the Householder vectors below it (each with an
+    /// implicit leading `1`), stored column by column
+    factors: Vec<T>,
+
+    /// The height of the factored matrix
+    height: usize,
+
+    /// The width of the factored matrix
+    width: usize,
+
+    /// The scaling factor of each reflection `I - τvvᵀ`
+    tau: Vec<T>,
+
+    /// Diagonal entries of `R` at or below this magnitude are treated as zero
+    tolerance: T,
+}
+
+impl<T: Float + Element, L: Layout> Matrix<T, L> {
+    /// Compute the QR decomposition of the matrix, using Householder reflections. The reflections
+    /// work on columns, so a `ColMajor` matrix is factored from a plain copy of its storage.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let a = matrix![f64; (2, 2); 0, 1; 1, 0];
+    /// let qr = a.qr();
+    ///
+    /// assert_eq!(qr.q() * qr.r(), a);
+    /// assert_eq!(a.to_col_major().qr().r(), qr.r());
+    /// ```
+    pub fn qr(&self) -> QR<T> {
+        let (m, n) = self.size();
+        let factors = self.to_col_major().data;
+
+        QR::new(factors, m, n, pivot_tolerance(&self.data, m.max(n)))
+    }
+}
+
+impl<T: Float + Element> Matrix<T> {
+    /// Find the least squares solution `X` to `AX = B`, minimising the 2-norm of each residual
+    /// column `AX - B`. Underdetermined systems (more columns than rows) get the solution of
+    /// minimum norm instead.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// // Fit `y = c0 + c1 * x` through (0, 1), (1, 3), (2, 5)
+    /// let a = matrix![f64; (3, 2); 1, 0; 1, 1; 1, 2];
+    /// let b = matrix![f64; (3, 1); 1; 3; 5];
+    /// let coefficients = a.least_squares(&b).unwrap();
+    ///
+    /// assert!((coefficients[(0, 0)] - 1.0).abs() < 1e-12);
+    /// assert!((coefficients[(1, 0)] - 2.0).abs() < 1e-12);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `b` does not have as many rows as `A`,
+    /// and with `MatrixError::Singular` if `A` does not have full rank
+    pub fn least_squares(&self, b: &Self) -> Result<Self, MatrixError> {
+        if b.height != self.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: b.size(),
+            });
+        }
+
+        if self.height >= self.width {
+            return self.qr().solve(b);
+        }
+
+        // With `Aᵀ = QR`, the minimum norm solution is `Q (Rᵀ)⁻¹ B`
+        let transposed = self.transposed();
+        let qr = transposed.qr();
+        if qr.is_rank_deficient() {
+            return Err(MatrixError::Singular);
+        }
+
+        let (m, k) = (self.height, b.width);
+        let mut y = b.data.clone();
+        let mut r_t = qr.r();
+        r_t.transpose();
+        forward_substitute(&r_t.data, m, &mut y, k, false);
+
+        y.resize(self.width * k, T::zero());
+        qr.apply_q(&mut y, k);
+
+        Ok(Self::from_vec((self.width, k), y))
+    }
+}
+
+impl<T: Float> QR<T> {
+    // Factors the `m * n` matrix stored column by column in `factors`
+    fn new(mut factors: Vec<T>, m: usize, n: usize, tolerance: T) -> Self {
+        let p = m.min(n);
+        let mut tau = vec![T::zero(); p];
+
+        for k in 0..p {
+            let (column, rest) = factors[k * m..].split_at_mut(m);
+            let alpha = column[k];
+            let tail = column[(k + 1)..]
+                .iter()
+                .fold(T::zero(), |acc, &x| acc.hypot(x));
+
+            if tail.is_zero() {
+                // Already upper triangular in this column, so `H = I`
+                continue;
+            }
+
+            let beta = -alpha.signum() * alpha.hypot(tail);
+            let scale = T::one() / (alpha - beta);
+            tau[k] = (beta - alpha) / beta;
+            column[k] = beta;
+            for x in &mut column[(k + 1)..] {
+                *x = *x * scale;
+            }
+
+            // Apply `H = I - τvvᵀ` to the remaining columns
+            for other in rest.chunks_mut(m) {
+                let dot = column[(k + 1)..]
+                    .iter()
+                    .zip(&other[(k + 1)..])
+                    .fold(other[k], |acc, (&v, &x)| acc + v * x);
+                let w = tau[k] * dot;
+
+                other[k] = other[k] - w;
+                for (x, &v) in other[(k + 1)..].iter_mut().zip(&column[(k + 1)..]) {
+                    *x = *x - w * v;
+                }
+            }
+        }
+
+        QR {
+            factors,
+            height: m,
+            width: n,
+            tau,
+            tolerance,
+        }
+    }
+
+    // Applies `H_k` to the `k` columns of `b` (stored row by row), where `b` has `self.height` rows
+    fn apply_reflection(&self, k: usize, b: &mut [T], cols: usize) {
+        let m = self.height;
+        let v = &self.factors[k * m..(k + 1) * m];
+
+        for c in 0..cols {
+            let dot = ((k + 1)..m).fold(b[k * cols + c], |acc, i| acc + v[i] * b[i * cols + c]);
+            let w = self.tau[k] * dot;
+
+            b[k * cols + c] = b[k * cols + c] - w;
+            for i in (k + 1)..m {
+                b[i * cols + c] = b[i * cols + c] - w * v[i];
+            }
+        }
+    }
+
+    // Overwrites `b` (`self.height` rows of `cols` entries) with `Qb`
+    fn apply_q(&self, b: &mut [T], cols: usize) {
+        for k in (0..self.tau.len()).rev() {
+            self.apply_reflection(k, b, cols);
+        }
+    }
+
+    // Overwrites `b` (`self.height` rows of `cols` entries) with `Qᵀb`
+    fn apply_q_transpose(&self, b: &mut [T], cols: usize) {
+        for k in 0..self.tau.len() {
+            self.apply_reflection(k, b, cols);
+        }
+    }
+
+    // The first `cols` columns of `Q`
+    fn q_columns(&self, cols: usize) -> Matrix<T> {
+        let mut q = Matrix::zeros((self.height, cols));
+        for i in 0..cols {
+            q.data[i * cols + i] = T::one();
+        }
+
+        self.apply_q(&mut q.data, cols);
+        q
+    }
+
+    /// The thin orthogonal factor, of size `m * min(m, n)`
+    pub fn q(&self) -> Matrix<T> {
+        self.q_columns(self.tau.len())
+    }
+
+    /// The full orthogonal factor, of size `m * m`
+    pub fn q_full(&self) -> Matrix<T> {
+        self.q_columns(self.height)
+    }
+
+    // The first `rows` rows of `R`
+    fn r_rows(&self, rows: usize) -> Matrix<T> {
+        let (m, n) = (self.height, self.width);
+        let mut r = Matrix::zeros((rows, n));
+
+        for i in 0..rows.min(n) {
+            for j in i..n {
+                r.data[i * n + j] = self.factors[j * m + i];
+            }
+        }
+
+        r
+    }
+
+    /// The thin upper triangular factor, of size `min(m, n) * n`
+    pub fn r(&self) -> Matrix<T> {
+        self.r_rows(self.tau.len())
+    }
+
+    /// The full upper triangular factor, of size `m * n`
+    pub fn r_full(&self) -> Matrix<T> {
+        self.r_rows(self.height)
+    }
+
+    /// Whether the factored matrix has (numerically) less than full rank
+    pub fn is_rank_deficient(&self) -> bool {
+        let m = self.height;
+        (0..self.tau.len()).any(|k| is_negligible(self.factors[k * m + k], self.tolerance))
+    }
+
+    /// Find the least squares solution `X` to `AX = B`, for a matrix with at least as many rows
+    /// as columns
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if `A` has more columns than rows or `b` does
+    /// not have as many rows as `A`, and with `MatrixError::Singular` if `A` does not have full
+    /// rank
+    pub fn solve(&self, b: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        let (m, n) = (self.height, self.width);
+        if b.height != m || m < n {
+            return Err(MatrixError::DimensionMismatch {
+                left: (m, n),
+                right: b.size(),
+            });
+        }
+
+        if self.is_rank_deficient() {
+            return Err(MatrixError::Singular);
+        }
+
+        let k = b.width;
+        let mut x = b.data.clone();
+        self.apply_q_transpose(&mut x, k);
+        x.truncate(n * k);
+        back_substitute(&self.r().data, n, &mut x, k, false);
+
+        Ok(Matrix::from_vec((n, k), x))
+    }
+}
+
+#[cfg(test)]
+mod qr_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    fn transposed(m: &Matrix<f64>) -> Matrix<f64> {
+        let mut m = m.clone();
+        m.transpose();
+        m
+    }
+
+    #[test]
+    fn test_factors() {
+        let tall = Matrix::from_vec(
+            (4, 3),
+            vec![
+                1.0, -1.0, 4.0, 1.0, 4.0, -2.0, 1.0, 4.0, 2.0, 1.0, -1.0, 0.0,
+            ],
+        );
+        let wide = transposed(&tall);
+
+        for a in &[tall, wide] {
+            let qr = a.qr();
+            let (q, r) = (qr.q(), qr.r());
+            let (q_full, r_full) = (qr.q_full(), qr.r_full());
+            let p = a.size().0.min(a.size().1);
+
+            assert_eq!(q.size(), (a.size().0, p));
+            assert_eq!(r.size(), (p, a.size().1));
+            assert_close(&(q.clone() * r.clone()), a);
+            assert_close(&(q_full.clone() * r_full.clone()), a);
+            assert_close(&(transposed(&q) * q), &Matrix::identity(p));
+            assert_close(
+                &(transposed(&q_full) * q_full),
+                &Matrix::identity(a.size().0),
+            );
+
+            for i in 0..r_full.size().0 {
+                for j in 0..i.min(r_full.size().1) {
+                    assert_eq!(r_full[(i, j)], 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_least_squares() {
+        // Noisy samples around `y = 1 + 2x`, compared against the normal equations
+        let a = Matrix::from_vec(
+            (5, 2),
+            vec![1.0, 0.0, 1.0, 1.0, 1.0, 2.0, 1.0, 3.0, 1.0, 4.0],
+        );
+        let b = Matrix::from_vec((5, 1), vec![1.1, 2.9, 5.2, 6.8, 9.1]);
+        let normal = (transposed(&a) * a.clone())
+            .solve(&(transposed(&a) * b.clone()))
+            .unwrap();
+
+        assert_close(&a.least_squares(&b).unwrap(), &normal);
+
+        // Minimum norm solution of an underdetermined system
+        let under = Matrix::from_vec((1, 2), vec![1.0, 1.0]);
+        let x = under
+            .least_squares(&Matrix::from_vec((1, 1), vec![2.0]))
+            .unwrap();
+        assert_close(&x, &Matrix::from_vec((2, 1), vec![1.0, 1.0]));
+    }
+
+    #[test]
+    fn test_failures() {
+        let deficient = Matrix::from_vec((3, 2), vec![1.0, 2.0, 2.0, 4.0, 3.0, 6.0]);
+
+        assert_eq!(
+            deficient.least_squares(&Matrix::zeros((3, 1))),
+            Err(MatrixError::Singular)
+        );
+        assert_eq!(
+            deficient.least_squares(&Matrix::zeros((2, 1))),
+            Err(MatrixError::DimensionMismatch {
+                left: (3, 2),
+                right: (2, 1)
+            })
+        );
+    }
+}
@@ -0,0 +1,540 @@
+use crate::{Matrix, MatrixError};
+
+use std::ops::{Index, IndexMut};
+
+use num_traits::{Float, Num};
+
+/// The real Schur decomposition `A = QTQᵀ` of a square matrix, computed by reducing to upper
+/// Hessenberg form and running the shifted (Francis double shift) QR algorithm.
+///
+/// `Q` is orthogonal and `T` is quasi upper triangular: it is upper triangular apart from `2 * 2`
+/// blocks on the diagonal, each of which holds a pair of complex conjugate eigenvalues.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// // A rotation by 90 degrees has the eigenvalues `±i`
+/// let schur = matrix![f64; (2, 2); 0, -1; 1, 0].schur().unwrap();
+/// let (re, im) = schur.eigenvalue_parts();
+///
+/// assert_eq!(re, &[0.0, 0.0]);
+/// assert_eq!(im, &[1.0, -1.0]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct Schur<T: Num> {
+    /// The orthogonal Schur vectors
+    q: Matrix<T>,
+
+    /// The quasi upper triangular Schur form
+    t: Matrix<T>,
+
+    /// The real parts of the eigenvalues, in the order they appear on the diagonal of `T`
+    re: Vec<T>,
+
+    /// The imaginary parts of the eigenvalues, where each conjugate pair is stored as `+, -`
+    im: Vec<T>,
+}
+
+// A square matrix stored row by row, indexed with `(row, col)`
+#[derive(Clone)]
+pub(crate) struct Grid<T> {
+    pub(crate) data: Vec<T>,
+    pub(crate) n: usize,
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i * self.n + j]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i * self.n + j]
+    }
+}
+
+// The working state of the QR algorithm, shared with the eigenvector back substitution
+pub(crate) struct Reduction<T> {
+    pub(crate) h: Grid<T>,
+    pub(crate) v: Grid<T>,
+    pub(crate) re: Vec<T>,
+    pub(crate) im: Vec<T>,
+}
+
+// The sum of the magnitudes of the entries on and above the subdiagonal of `h`
+pub(crate) fn hessenberg_norm<T: Float>(h: &Grid<T>) -> T {
+    let n = h.n;
+    (0..n)
+        .flat_map(|i| (i.saturating_sub(1)..n).map(move |j| (i, j)))
+        .fold(T::zero(), |acc, index| acc + h[index].abs())
+}
+
+fn constant<T: Float>(value: f64) -> T {
+    T::from(value).expect("Constant is representable")
+}
+
+// Reduces `h` to upper Hessenberg form with Householder similarity transformations, returning
+// the accumulated orthogonal transformation
+fn hessenberg<T: Float>(h: &mut Grid<T>) -> Grid<T> {
+    let n = h.n;
+    let mut ort = vec![T::zero(); n];
+
+    for m in 1..n.saturating_sub(1) {
+        let scale = (m..n).fold(T::zero(), |acc, i| acc + h[(i, m - 1)].abs());
+        if scale.is_zero() {
+            continue;
+        }
+
+        let mut sum = T::zero();
+        for i in (m..n).rev() {
+            ort[i] = h[(i, m - 1)] / scale;
+            sum = sum + ort[i] * ort[i];
+        }
+
+        let g = if ort[m] > T::zero() {
+            -sum.sqrt()
+        } else {
+            sum.sqrt()
+        };
+        sum = sum - ort[m] * g;
+        ort[m] = ort[m] - g;
+
+        // `H = (I - uuᵀ / h) H (I - uuᵀ / h)`
+        for j in m..n {
+            let f = (m..n)
+                .rev()
+                .fold(T::zero(), |acc, i| acc + ort[i] * h[(i, j)])
+                / sum;
+            for i in m..n {
+                h[(i, j)] = h[(i, j)] - f * ort[i];
+            }
+        }
+
+        for i in 0..n {
+            let f = (m..n)
+                .rev()
+                .fold(T::zero(), |acc, j| acc + ort[j] * h[(i, j)])
+                / sum;
+            for j in m..n {
+                h[(i, j)] = h[(i, j)] - f * ort[j];
+            }
+        }
+
+        ort[m] = scale * ort[m];
+        h[(m, m - 1)] = scale * g;
+    }
+
+    let mut v = Grid {
+        data: Matrix::identity(n).data,
+        n,
+    };
+
+    for m in (1..n.saturating_sub(1)).rev() {
+        if h[(m, m - 1)].is_zero() {
+            continue;
+        }
+
+        for i in (m + 1)..n {
+            ort[i] = h[(i, m - 1)];
+        }
+
+        for j in m..n {
+            let g = (m..n).fold(T::zero(), |acc, i| acc + ort[i] * v[(i, j)]);
+            // Double division avoids possible underflow
+            let g = (g / ort[m]) / h[(m, m - 1)];
+            for i in m..n {
+                v[(i, j)] = v[(i, j)] + g * ort[i];
+            }
+        }
+    }
+
+    // Clear the Householder vectors left below the subdiagonal
+    for i in 0..n {
+        for j in 0..i.saturating_sub(1) {
+            h[(i, j)] = T::zero();
+        }
+    }
+
+    v
+}
+
+// Runs the shifted QR algorithm on the upper Hessenberg `h`, accumulating into `v`.
+// Adapted from the `hqr2` routine of EISPACK, by way of JAMA.
+fn hessenberg_qr<T: Float>(
+    h: &mut Grid<T>,
+    v: &mut Grid<T>,
+) -> Result<(Vec<T>, Vec<T>), MatrixError> {
+    let nn = h.n;
+    let eps = T::epsilon();
+    let two = constant::<T>(2.0);
+    let max_iterations = 30 * nn.max(10);
+
+    let mut re = vec![T::zero(); nn];
+    let mut im = vec![T::zero(); nn];
+    let mut exshift = T::zero();
+    let (mut p, mut q, mut r, mut s, mut z);
+    let (mut w, mut x, mut y);
+
+    let norm = hessenberg_norm(h);
+
+    let mut iter = 0;
+    let mut total = 0;
+    let mut n = nn;
+
+    while n > 0 {
+        let last = n - 1;
+
+        // Look for a single small subdiagonal element
+        let mut l = last;
+        while l > 0 {
+            s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+            if s.is_zero() {
+                s = norm;
+            }
+            // Not strict, so that an all zero matrix deflates straight away
+            if h[(l, l - 1)].abs() <= eps * s {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == last {
+            // One root found
+            h[(last, last)] = h[(last, last)] + exshift;
+            re[last] = h[(last, last)];
+            im[last] = T::zero();
+            n -= 1;
+            iter = 0;
+        } else if l + 1 == last {
+            // Two roots found
+            let prev = last - 1;
+            w = h[(last, prev)] * h[(prev, last)];
+            p = (h[(prev, prev)] - h[(last, last)]) / two;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[(last, last)] = h[(last, last)] + exshift;
+            h[(prev, prev)] = h[(prev, prev)] + exshift;
+            x = h[(last, last)];
+
+            if q >= T::zero() {
+                // Real pair, so split the block with a rotation
+                z = if p >= T::zero() { p + z } else { p - z };
+                re[prev] = x + z;
+                re[last] = if z.is_zero() { re[prev] } else { x - w / z };
+                im[prev] = T::zero();
+                im[last] = T::zero();
+
+                x = h[(last, prev)];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = (p * p + q * q).sqrt();
+                p = p / r;
+                q = q / r;
+
+                for j in prev..nn {
+                    z = h[(prev, j)];
+                    h[(prev, j)] = q * z + p * h[(last, j)];
+                    h[(last, j)] = q * h[(last, j)] - p * z;
+                }
+
+                for i in 0..=last {
+                    z = h[(i, prev)];
+                    h[(i, prev)] = q * z + p * h[(i, last)];
+                    h[(i, last)] = q * h[(i, last)] - p * z;
+                }
+
+                for i in 0..nn {
+                    z = v[(i, prev)];
+                    v[(i, prev)] = q * z + p * v[(i, last)];
+                    v[(i, last)] = q * v[(i, last)] - p * z;
+                }
+            } else {
+                // Complex pair
+                re[prev] = x + p;
+                re[last] = x + p;
+                im[prev] = z;
+                im[last] = -z;
+            }
+
+            n -= 2;
+            iter = 0;
+        } else {
+            // No convergence yet
+            total += 1;
+            if total > max_iterations {
+                return Err(MatrixError::NotConverged);
+            }
+
+            // Form the shift
+            x = h[(last, last)];
+            y = T::zero();
+            w = T::zero();
+            if l < last {
+                y = h[(last - 1, last - 1)];
+                w = h[(last, last - 1)] * h[(last - 1, last)];
+            }
+
+            // Wilkinson's original ad hoc shift
+            if iter == 10 {
+                exshift = exshift + x;
+                for i in 0..=last {
+                    h[(i, i)] = h[(i, i)] - x;
+                }
+                s = h[(last, last - 1)].abs() + h[(last - 1, last - 2)].abs();
+                x = constant::<T>(0.75) * s;
+                y = x;
+                w = constant::<T>(-0.4375) * s * s;
+            }
+
+            // MATLAB's ad hoc shift
+            if iter == 30 {
+                s = (y - x) / two;
+                s = s * s + w;
+                if s > T::zero() {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=last {
+                        h[(i, i)] = h[(i, i)] - s;
+                    }
+                    exshift = exshift + s;
+                    x = constant(0.964);
+                    y = x;
+                    w = x;
+                }
+            }
+
+            iter += 1;
+
+            // Look for two consecutive small subdiagonal elements
+            let mut m = last - 2;
+            loop {
+                z = h[(m, m)];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+                q = h[(m + 1, m + 1)] - z - r - s;
+                r = h[(m + 2, m + 1)];
+                s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+
+                if m == l {
+                    break;
+                }
+
+                let lhs = h[(m, m - 1)].abs() * (q.abs() + r.abs());
+                let rhs =
+                    eps * (p.abs() * (h[(m - 1, m - 1)].abs() + z.abs() + h[(m + 1, m + 1)].abs()));
+                if lhs < rhs {
+                    break;
+                }
+                m -= 1;
+            }
+
+            for i in (m + 2)..=last {
+                h[(i, i - 2)] = T::zero();
+                if i > m + 2 {
+                    h[(i, i - 3)] = T::zero();
+                }
+            }
+
+            // Double QR step involving rows `l..=last` and columns `m..=last`
+            for k in m..last {
+                let not_last = k != last - 1;
+                if k != m {
+                    p = h[(k, k - 1)];
+                    q = h[(k + 1, k - 1)];
+                    r = if not_last {
+                        h[(k + 2, k - 1)]
+                    } else {
+                        T::zero()
+                    };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x.is_zero() {
+                        continue;
+                    }
+                    p = p / x;
+                    q = q / x;
+                    r = r / x;
+                }
+
+                s = (p * p + q * q + r * r).sqrt();
+                if p < T::zero() {
+                    s = -s;
+                }
+
+                if s.is_zero() {
+                    continue;
+                }
+
+                if k != m {
+                    h[(k, k - 1)] = -s * x;
+                } else if l != m {
+                    h[(k, k - 1)] = -h[(k, k - 1)];
+                }
+
+                p = p + s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q = q / p;
+                r = r / p;
+
+                for j in k..nn {
+                    p = h[(k, j)] + q * h[(k + 1, j)];
+                    if not_last {
+                        p = p + r * h[(k + 2, j)];
+                        h[(k + 2, j)] = h[(k + 2, j)] - p * z;
+                    }
+                    h[(k, j)] = h[(k, j)] - p * x;
+                    h[(k + 1, j)] = h[(k + 1, j)] - p * y;
+                }
+
+                for i in 0..=last.min(k + 3) {
+                    p = x * h[(i, k)] + y * h[(i, k + 1)];
+                    if not_last {
+                        p = p + z * h[(i, k + 2)];
+                        h[(i, k + 2)] = h[(i, k + 2)] - p * r;
+                    }
+                    h[(i, k)] = h[(i, k)] - p;
+                    h[(i, k + 1)] = h[(i, k + 1)] - p * q;
+                }
+
+                for i in 0..nn {
+                    p = x * v[(i, k)] + y * v[(i, k + 1)];
+                    if not_last {
+                        p = p + z * v[(i, k + 2)];
+                        v[(i, k + 2)] = v[(i, k + 2)] - p * r;
+                    }
+                    v[(i, k)] = v[(i, k)] - p;
+                    v[(i, k + 1)] = v[(i, k + 1)] - p * q;
+                }
+            }
+        }
+    }
+
+    Ok((re, im))
+}
+
+// Computes the real Schur form of a square matrix
+pub(crate) fn reduce<T: Float>(a: &Matrix<T>) -> Result<Reduction<T>, MatrixError> {
+    if a.height != a.width {
+        return Err(MatrixError::NotSquare);
+    }
+
+    let mut h = Grid {
+        data: a.data.clone(),
+        n: a.height,
+    };
+    let mut v = hessenberg(&mut h);
+    let (re, im) = hessenberg_qr(&mut h, &mut v)?;
+
+    Ok(Reduction { h, v, re, im })
+}
+
+impl<T: Float> Matrix<T> {
+    /// Compute the real Schur decomposition of a square matrix
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let schur = matrix![f64; (2, 2); 2, 0; 0, 1].schur().unwrap();
+    ///
+    /// assert_eq!(schur.eigenvalue_parts(), (&[2.0, 1.0][..], &[0.0, 0.0][..]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::NotConverged` if the QR algorithm does not converge
+    pub fn schur(&self) -> Result<Schur<T>, MatrixError> {
+        let Reduction {
+            mut h, v, re, im, ..
+        } = reduce(self)?;
+        let n = h.n;
+
+        // Clear what is left of the bulges below the subdiagonal, and the negligible subdiagonal
+        // entries that the iteration deflated on. The second eigenvalue of a complex pair has a
+        // negative imaginary part, and keeps the subdiagonal entry of its block.
+        for i in 1..n {
+            for j in 0..(i - 1) {
+                h[(i, j)] = T::zero();
+            }
+            if im[i] >= T::zero() {
+                h[(i, i - 1)] = T::zero();
+            }
+        }
+
+        Ok(Schur {
+            q: Self::from_vec(n, v.data),
+            t: Self::from_vec(n, h.data),
+            re,
+            im,
+        })
+    }
+}
+
+impl<T: Float> Schur<T> {
+    /// The orthogonal matrix of Schur vectors `Q`
+    pub fn q(&self) -> Matrix<T> {
+        self.q.clone()
+    }
+
+    /// The quasi upper triangular Schur form `T`
+    pub fn t(&self) -> Matrix<T> {
+        self.t.clone()
+    }
+
+    /// The real and imaginary parts of the eigenvalues, in the order they appear on the diagonal
+    /// of `T`. Complex eigenvalues come in conjugate pairs, with the positive imaginary part first.
+    pub fn eigenvalue_parts(&self) -> (&[T], &[T]) {
+        (&self.re, &self.im)
+    }
+}
+
+#[cfg(test)]
+mod schur_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_schur_form() {
+        let a = Matrix::from_vec(
+            4,
+            vec![
+                4.0, -1.0, 2.0, 3.0, 1.0, 0.0, -2.0, 5.0, 3.0, 1.0, 1.0, -4.0, 0.5, 2.0, -1.0, 2.0,
+            ],
+        );
+        let schur = a.schur().unwrap();
+        let (q, t) = (schur.q(), schur.t());
+        let mut q_t = q.clone();
+        q_t.transpose();
+
+        assert_close(&(q_t.clone() * q.clone()), &Matrix::identity(4));
+        assert_close(&(q * t.clone() * q_t), &a);
+
+        // Quasi upper triangular, and the eigenvalues sum to the trace
+        for i in 2..4 {
+            for j in 0..(i - 1) {
+                assert_eq!(t[(i, j)], 0.0);
+            }
+        }
+
+        let (re, _) = schur.eigenvalue_parts();
+        assert!((re.iter().sum::<f64>() - 7.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_failures() {
+        assert_eq!(
+            Matrix::<f64>::zeros((2, 3)).schur(),
+            Err(MatrixError::NotSquare)
+        );
+        assert!(Matrix::<f64>::zeros(0).schur().is_ok());
+        assert!(Matrix::from_vec(1, vec![5.0]).schur().is_ok());
+    }
+}
@@ -0,0 +1,183 @@
+use super::cholesky::cholesky_factor;
+use super::triangular::{back_substitute, back_substitute_transpose, forward_substitute};
+use super::{is_negligible, pivot_tolerance};
+use crate::{Element, Matrix, MatrixError};
+
+use num_traits::Float;
+
+// The structure of a square matrix, from cheapest to most expensive to solve with
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Structure {
+    Diagonal,
+    UpperTriangular,
+    LowerTriangular,
+    Symmetric,
+    General,
+}
+
+impl<T: Float + Element> Matrix<T> {
+    /// Solve the square system `AX = B` for `X`, where each column of `b` is a separate right
+    /// hand side (so a single vector is a `n * 1` matrix).
+    ///
+    /// Diagonal and triangular systems are solved directly by substitution, and symmetric
+    /// positive definite systems with a Cholesky factorization. Everything else falls back to an
+    /// LU decomposition with partial pivoting.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// // Upper triangular, so this is solved by back substitution
+    /// let a = matrix![f64; (2, 2); 2, 1; 0, 4];
+    /// let b = matrix![f64; (2, 2); 4, 1; 8, 0];
+    ///
+    /// assert_eq!(a.solve(&b), Ok(matrix![f64; (2, 2); 1, 0.5; 2, 0]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if `A` is not square, with
+    /// `MatrixError::DimensionMismatch` if `b` does not have as many rows as `A`, and with
+    /// `MatrixError::Singular` if `A` is singular
+    pub fn solve(&self, b: &Self) -> Result<Self, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
+        }
+
+        if b.height != self.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: b.size(),
+            });
+        }
+
+        let n = self.height;
+        let k = b.width;
+        let tolerance = pivot_tolerance(&self.data, n);
+        let mut x = b.data.clone();
+
+        let structure = self.structure();
+        let triangular = structure != Structure::General && structure != Structure::Symmetric;
+        if triangular && (0..n).any(|i| is_negligible(self.data[i * n + i], tolerance)) {
+            return Err(MatrixError::Singular);
+        }
+
+        match structure {
+            Structure::Diagonal => {
+                for i in 0..n {
+                    for c in 0..k {
+                        x[i * k + c] = x[i * k + c] / self.data[i * n + i];
+                    }
+                }
+            }
+            Structure::UpperTriangular => back_substitute(&self.data, n, &mut x, k, false),
+            Structure::LowerTriangular => forward_substitute(&self.data, n, &mut x, k, false),
+            Structure::Symmetric => match cholesky_factor(&self.data, n, tolerance) {
+                Ok(l) => {
+                    forward_substitute(&l, n, &mut x, k, false);
+                    back_substitute_transpose(&l, n, &mut x, k, false);
+                }
+                // Symmetric, but not positive definite
+                Err(_) => return self.lu()?.solve(b),
+            },
+            Structure::General => return self.lu()?.solve(b),
+        }
+
+        Ok(Self::from_vec((n, k), x))
+    }
+
+    /// Solve the square system `Ax = b` for a single right hand side `b`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let a = matrix![f64; (2, 2); 2, 0; 0, 4];
+    ///
+    /// assert_eq!(a.solve_vec(&[3.0, 2.0]), Ok(vec![1.5, 0.5]));
+    /// ```
+    /// # Failure
+    /// Fails under the same conditions as `Matrix::solve`
+    pub fn solve_vec(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        self.solve(&Self::from_slice((b.len(), 1), b))
+            .map(|x| x.data)
+    }
+
+    // Classifies a square matrix, by exact comparison against zero and its transpose
+    fn structure(&self) -> Structure {
+        let n = self.height;
+        let mut upper = true;
+        let mut lower = true;
+        let mut symmetric = true;
+
+        for i in 0..n {
+            for j in 0..i {
+                let below = self.data[i * n + j];
+                let above = self.data[j * n + i];
+
+                upper &= below.is_zero();
+                lower &= above.is_zero();
+                symmetric &= below == above;
+            }
+        }
+
+        match (upper, lower, symmetric) {
+            (true, true, _) => Structure::Diagonal,
+            (true, false, _) => Structure::UpperTriangular,
+            (false, true, _) => Structure::LowerTriangular,
+            (false, false, true) => Structure::Symmetric,
+            (false, false, false) => Structure::General,
+        }
+    }
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use super::Structure;
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    fn assert_solves(a: &Matrix<f64>, b: &Matrix<f64>) {
+        let x = a.solve(b).expect("Could not solve the system");
+        assert_close(&(a * &x), b);
+    }
+
+    #[test]
+    fn test_structures() {
+        let diagonal = Matrix::diag(vec![2.0, 4.0, 5.0]);
+        let upper = Matrix::from_vec(3, vec![1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 0.0, 0.0, 6.0]);
+        let lower = Matrix::from_vec(3, vec![1.0, 0.0, 0.0, 2.0, 4.0, 0.0, 3.0, 5.0, 6.0]);
+        let spd = Matrix::from_vec(3, vec![4.0, 1.0, 1.0, 1.0, 3.0, 0.0, 1.0, 0.0, 2.0]);
+        let indefinite = Matrix::from_vec(2, vec![0.0, 1.0, 1.0, 0.0]);
+        let general = Matrix::from_vec(3, vec![0.0, 2.0, 1.0, 3.0, 0.0, 1.0, 1.0, 1.0, 0.0]);
+
+        assert_eq!(diagonal.structure(), Structure::Diagonal);
+        assert_eq!(upper.structure(), Structure::UpperTriangular);
+        assert_eq!(lower.structure(), Structure::LowerTriangular);
+        assert_eq!(spd.structure(), Structure::Symmetric);
+        assert_eq!(indefinite.structure(), Structure::Symmetric);
+        assert_eq!(general.structure(), Structure::General);
+
+        let b = Matrix::from_vec((3, 2), vec![1.0, -1.0, 2.0, 0.5, 3.0, 4.0]);
+        for a in &[diagonal, upper, lower, spd, general] {
+            assert_solves(a, &b);
+        }
+
+        assert_solves(&indefinite, &Matrix::from_vec((2, 1), vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_failures() {
+        let singular_diag = Matrix::diag(vec![1.0, 0.0]);
+        let singular = Matrix::from_vec(2, vec![1.0, 2.0, 2.0, 4.0]);
+        let b = Matrix::from_vec((2, 1), vec![1.0, 1.0]);
+
+        assert_eq!(singular_diag.solve(&b), Err(MatrixError::Singular));
+        assert_eq!(singular.solve(&b), Err(MatrixError::Singular));
+        assert_eq!(
+            Matrix::<f64>::zeros((2, 3)).solve(&b),
+            Err(MatrixError::NotSquare)
+        );
+        assert_eq!(
+            Matrix::<f64>::identity(3).solve_vec(&[1.0, 2.0]),
+            Err(MatrixError::DimensionMismatch {
+                left: (3, 3),
+                right: (2, 1)
+            })
+        );
+    }
+}
@@ -0,0 +1,460 @@
+use crate::{Element, Matrix, MatrixError};
+
+use num_traits::{Float, Num};
+
+// Upper bound on the number of Jacobi sweeps, which in practice converge in well under 20
+const MAX_SWEEPS: usize = 60;
+
+/// The singular value decomposition `A = UΣVᵀ` of an `m * n` matrix, computed with one-sided
+/// Jacobi rotations.
+///
+/// `U` and `V` are orthogonal and the singular values (the diagonal of `Σ`) are non-negative and
+/// sorted in decreasing order. `Matrix::svd` computes the thin form, where `U` is `m * k` and `Vᵀ`
+/// is `k * n` with `k = min(m, n)`, and `Matrix::svd_full` the full form, where `U` is `m * m`
+/// and `Vᵀ` is `n * n`.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (3, 2); 0, 2; 3, 0; 0, 0];
+/// let svd = a.svd().unwrap();
+///
+/// assert_eq!(svd.singular_values(), &[3.0, 2.0]);
+/// assert_eq!(svd.u() * svd.sigma() * svd.v_t(), a);
+/// assert_eq!(a.svd_full().unwrap().u().size(), (3, 3));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SVD<T: Num> {
+    /// The left singular vectors, as columns
+    u: Matrix<T>,
+
+    /// The singular values, in decreasing order
+    singular_values: Vec<T>,
+
+    /// The right singular vectors, as rows
+    v_t: Matrix<T>,
+}
+
+impl<T: Float + Element> Matrix<T> {
+    /// Compute the thin singular value decomposition of the matrix
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge (as for entries
+    /// that are not finite)
+    pub fn svd(&self) -> Result<SVD<T>, MatrixError> {
+        SVD::new(self, false)
+    }
+
+    /// Compute the full singular value decomposition of the matrix
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn svd_full(&self) -> Result<SVD<T>, MatrixError> {
+        SVD::new(self, true)
+    }
+
+    /// The singular values of the matrix, in decreasing order
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// assert_eq!(matrix![f64; (2, 2); 0, -4; 1, 0].singular_values(), Ok(vec![4.0, 1.0]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn singular_values(&self) -> Result<Vec<T>, MatrixError> {
+        // The left and right vectors are cheap next to the sweeps, so there is no separate path
+        Ok(self.svd()?.singular_values)
+    }
+
+    /// The numerical rank of the matrix, that is the number of singular values above `tol`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let a = matrix![f64; (3, 3); 1, 2, 3; 4, 5, 6; 7, 8, 9];
+    ///
+    /// assert_eq!(a.rank(1e-10), Ok(2));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn rank(&self, tol: T) -> Result<usize, MatrixError> {
+        Ok(self.singular_values()?.iter().filter(|&&s| s > tol).count())
+    }
+
+    /// The Moore-Penrose pseudo-inverse of the matrix, `VΣ⁺Uᵀ`. Singular values below
+    /// `max(m, n) * ε * σ₁` are treated as zero.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let a = matrix![f64; (2, 3); 1, 0, 0; 0, 2, 0];
+    ///
+    /// assert_eq!(a.pinv(), Ok(matrix![f64; (3, 2); 1, 0; 0, 0.5; 0, 0]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn pinv(&self) -> Result<Self, MatrixError> {
+        let svd = self.svd()?;
+        let k = svd.singular_values.len();
+        let cutoff = svd.default_tolerance(self.size());
+        let SVD {
+            u,
+            singular_values,
+            v_t: mut scaled,
+        } = svd;
+
+        // Scale the columns of `V` (rows of `Vᵀ`) by the inverted singular values
+        for (i, &s) in singular_values.iter().enumerate() {
+            let factor = if s > cutoff { T::one() / s } else { T::zero() };
+            for x in &mut scaled.data[i * self.width..(i + 1) * self.width] {
+                *x = *x * factor;
+            }
+        }
+
+        // `(Σ⁺Vᵀ)ᵀUᵀ`, computed directly to avoid transposing both factors
+        let (m, n) = self.size();
+        let mut pinv = Self::zeros((n, m));
+        for i in 0..n {
+            for j in 0..m {
+                pinv.data[i * m + j] = (0..k).fold(T::zero(), |acc, l| {
+                    acc + scaled.data[l * n + i] * u.data[j * k + l]
+                });
+            }
+        }
+
+        Ok(pinv)
+    }
+
+    /// The 2-norm (spectral norm) of the matrix, which is its largest singular value
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// assert_eq!(matrix![f64; (2, 2); 3, 0; 0, -5].norm2(), Ok(5.0));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn norm2(&self) -> Result<T, MatrixError> {
+        Ok(self
+            .singular_values()?
+            .first()
+            .copied()
+            .unwrap_or_else(T::zero))
+    }
+
+    /// The 2-norm condition number of the matrix, `σ₁ / σₖ`. This is huge (or infinite) for rank
+    /// deficient matrices.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// assert_eq!(matrix![f64; (2, 2); 1, 0; 0, 4].condition_number(), Ok(4.0));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotConverged` if the Jacobi sweeps do not converge
+    pub fn condition_number(&self) -> Result<T, MatrixError> {
+        let values = self.singular_values()?;
+        Ok(match (values.first(), values.last()) {
+            (Some(&largest), Some(&smallest)) => largest / smallest,
+            _ => T::zero(),
+        })
+    }
+}
+
+impl<T: Float + Element> SVD<T> {
+    fn new(a: &Matrix<T>, full: bool) -> Result<Self, MatrixError> {
+        let (m, n) = a.size();
+
+        if m < n {
+            // Decompose `Aᵀ = VΣUᵀ` instead, so the Jacobi sweeps run on the shorter dimension
+            let transposed = a.transposed();
+            let svd = Self::new(&transposed, full)?;
+
+            let mut u = svd.v_t;
+            let mut v_t = svd.u;
+            u.transpose();
+            v_t.transpose();
+
+            return Ok(SVD {
+                u,
+                singular_values: svd.singular_values,
+                v_t,
+            });
+        }
+
+        // Columns of `A`, which the rotations make mutually orthogonal
+        let mut u: Vec<Vec<T>> = (0..n)
+            .map(|col| (0..m).map(|row| a.data[row * n + col]).collect())
+            .collect();
+        let mut v: Vec<Vec<T>> = (0..n)
+            .map(|col| {
+                (0..n)
+                    .map(|row| if row == col { T::one() } else { T::zero() })
+                    .collect()
+            })
+            .collect();
+
+        // Columns `p` and `q` count as orthogonal once their inner product is negligible next to
+        // their lengths. This fails for entries that are not finite.
+        let orthogonal = |alpha: T, beta: T, gamma: T| {
+            gamma.is_zero() || gamma.abs() <= T::epsilon() * (alpha * beta).sqrt()
+        };
+
+        for _ in 0..MAX_SWEEPS {
+            let mut rotated = false;
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let alpha = dot(&u[p], &u[p]);
+                    let beta = dot(&u[q], &u[q]);
+                    let gamma = dot(&u[p], &u[q]);
+
+                    if orthogonal(alpha, beta, gamma) {
+                        continue;
+                    }
+
+                    rotated = true;
+                    let two = T::one() + T::one();
+                    let zeta = (beta - alpha) / (two * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (T::one() + zeta * zeta).sqrt());
+                    let c = T::one() / (T::one() + t * t).sqrt();
+                    let s = c * t;
+
+                    rotate(&mut u, p, q, c, s);
+                    rotate(&mut v, p, q, c, s);
+                }
+            }
+
+            if !rotated {
+                break;
+            }
+        }
+
+        let converged = (0..n).all(|p| {
+            ((p + 1)..n)
+                .all(|q| orthogonal(dot(&u[p], &u[p]), dot(&u[q], &u[q]), dot(&u[p], &u[q])))
+        });
+        if !converged {
+            return Err(MatrixError::NotConverged);
+        }
+
+        let mut values: Vec<T> = u.iter().map(|col| dot(col, col).sqrt()).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| {
+            values[j]
+                .partial_cmp(&values[i])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut u: Vec<Vec<T>> = order.iter().map(|&i| u[i].clone()).collect();
+        let v: Vec<Vec<T>> = order.iter().map(|&i| v[i].clone()).collect();
+        values = order.iter().map(|&i| values[i]).collect();
+
+        // Normalise the left vectors, leaving those of zero singular values to be completed
+        let cutoff = T::epsilon()
+            * T::from(m).unwrap_or_else(T::one)
+            * values.first().copied().unwrap_or_else(T::zero);
+        let mut valid = Vec::with_capacity(m);
+        for (col, &s) in u.iter_mut().zip(&values) {
+            if s > cutoff && s > T::min_positive_value() {
+                for x in col.iter_mut() {
+                    *x = *x / s;
+                }
+                valid.push(true);
+            } else {
+                valid.push(false);
+            }
+        }
+
+        if full {
+            u.resize(m, vec![T::zero(); m]);
+            valid.resize(m, false);
+        }
+        complete_basis(&mut u, &valid, m);
+
+        let width = u.len();
+        let mut u_matrix = Matrix::zeros((m, width));
+        for (j, col) in u.iter().enumerate() {
+            for (i, &x) in col.iter().enumerate() {
+                u_matrix.data[i * width + j] = x;
+            }
+        }
+
+        Ok(SVD {
+            u: u_matrix,
+            singular_values: values,
+            v_t: Matrix::from_vec(n, v.into_iter().flatten().collect()),
+        })
+    }
+
+    // The default cutoff below which singular values are treated as zero
+    fn default_tolerance(&self, (m, n): (usize, usize)) -> T {
+        let largest = self
+            .singular_values
+            .first()
+            .copied()
+            .unwrap_or_else(T::zero);
+        T::from(m.max(n)).unwrap_or_else(T::one) * T::epsilon() * largest
+    }
+
+    /// The left singular vectors `U`, as columns
+    pub fn u(&self) -> Matrix<T> {
+        self.u.clone()
+    }
+
+    /// The singular values, in decreasing order
+    pub fn singular_values(&self) -> &[T] {
+        &self.singular_values
+    }
+
+    /// The matrix `Σ`, with the singular values on its diagonal, sized to fit between `U` and
+    /// `Vᵀ`
+    pub fn sigma(&self) -> Matrix<T> {
+        let (rows, cols) = (self.u.width, self.v_t.height);
+        let mut sigma = Matrix::zeros((rows, cols));
+
+        for (i, &s) in self.singular_values.iter().enumerate() {
+            sigma.data[i * cols + i] = s;
+        }
+
+        sigma
+    }
+
+    /// The right singular vectors `Vᵀ`, as rows
+    pub fn v_t(&self) -> Matrix<T> {
+        self.v_t.clone()
+    }
+}
+
+fn dot<T: Float>(left: &[T], right: &[T]) -> T {
+    left.iter()
+        .zip(right)
+        .fold(T::zero(), |acc, (&l, &r)| acc + l * r)
+}
+
+// Applies the rotation `[c s; -s c]` to columns `p` and `q`
+fn rotate<T: Float>(cols: &mut [Vec<T>], p: usize, q: usize, c: T, s: T) {
+    let (head, tail) = cols.split_at_mut(q);
+    for (x, y) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+        let (a, b) = (*x, *y);
+        *x = c * a - s * b;
+        *y = s * a + c * b;
+    }
+}
+
+// Replaces every column not marked `valid` with a unit vector orthogonal to all of the others,
+// drawn from the standard basis of length `m`
+fn complete_basis<T: Float>(cols: &mut [Vec<T>], valid: &[bool], m: usize) {
+    let mut basis: Vec<usize> = (0..cols.len()).filter(|&j| valid[j]).collect();
+
+    for j in (0..cols.len()).filter(|&j| !valid[j]) {
+        // Pick the standard basis vector that is furthest from the current span
+        let mut best = vec![T::zero(); m];
+        let mut best_norm = -T::one();
+
+        for e in 0..m {
+            let mut candidate = vec![T::zero(); m];
+            candidate[e] = T::one();
+
+            // Orthogonalise twice, which keeps the result orthogonal to working precision
+            for _ in 0..2 {
+                for &b in &basis {
+                    let projection = dot(&cols[b], &candidate);
+                    for (x, &y) in candidate.iter_mut().zip(&cols[b]) {
+                        *x = *x - projection * y;
+                    }
+                }
+            }
+
+            let norm = dot(&candidate, &candidate).sqrt();
+            if norm > best_norm {
+                best_norm = norm;
+                best = candidate;
+            }
+        }
+
+        for x in &mut best {
+            *x = *x / best_norm;
+        }
+
+        cols[j] = best;
+        basis.push(j);
+    }
+}
+
+#[cfg(test)]
+mod svd_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    fn transposed(m: &Matrix<f64>) -> Matrix<f64> {
+        let mut m = m.clone();
+        m.transpose();
+        m
+    }
+
+    fn assert_orthonormal_columns(m: &Matrix<f64>) {
+        assert_close(&(transposed(m) * m.clone()), &Matrix::identity(m.size().1));
+    }
+
+    #[test]
+    fn test_decomposition() {
+        let tall = Matrix::from_vec(
+            (4, 3),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0, -1.0, 0.5, 2.0],
+        );
+        let deficient = Matrix::from_vec(
+            (4, 3),
+            vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 1.0, 1.0, 1.0, 3.0, 4.0],
+        );
+
+        for a in &[
+            tall.clone(),
+            transposed(&tall),
+            deficient.clone(),
+            transposed(&deficient),
+        ] {
+            for svd in &[a.svd().unwrap(), a.svd_full().unwrap()] {
+                assert_close(&(svd.u() * svd.sigma() * svd.v_t()), a);
+                assert_orthonormal_columns(&svd.u());
+                assert_orthonormal_columns(&transposed(&svd.v_t()));
+
+                let values = svd.singular_values();
+                assert!(values.windows(2).all(|w| w[0] >= w[1]));
+                assert!(values.iter().all(|&s| s >= 0.0));
+            }
+
+            let full = a.svd_full().unwrap();
+            assert_eq!(full.u().size(), (a.size().0, a.size().0));
+            assert_eq!(full.v_t().size(), (a.size().1, a.size().1));
+        }
+
+        assert_eq!(tall.rank(1e-10), Ok(3));
+        assert_eq!(deficient.rank(1e-10), Ok(2));
+    }
+
+    #[test]
+    fn test_derived() {
+        let a = Matrix::from_vec(2, vec![3.0, 0.0, 4.0, 5.0]);
+        let singular = Matrix::from_vec(2, vec![1.0, 2.0, 2.0, 4.0]);
+
+        assert!((a.norm2().unwrap() - 45f64.sqrt()).abs() < 1e-12);
+        assert!((a.condition_number().unwrap() - 3.0).abs() < 1e-12);
+        assert_close(&a.pinv().unwrap(), &a.inverse().unwrap());
+        assert!(singular.condition_number().unwrap() > 1e15);
+
+        // The defining property of the pseudo-inverse, `AA⁺A = A`
+        let pinv = singular.pinv().unwrap();
+        assert_close(
+            &(singular.clone() * pinv.clone() * singular.clone()),
+            &singular,
+        );
+        assert_close(&(pinv.clone() * singular * pinv.clone()), &pinv);
+
+        let empty: Matrix<f64> = Matrix::zeros(0);
+        assert_eq!(empty.norm2(), Ok(0.0));
+        assert_eq!(empty.rank(0.0), Ok(0));
+    }
+
+    #[test]
+    fn test_not_converged() {
+        let a = Matrix::from_vec(2, vec![1.0, f64::NAN, f64::NAN, 1.0]);
+
+        assert_eq!(a.svd(), Err(MatrixError::NotConverged));
+        assert_eq!(transposed(&a).svd_full(), Err(MatrixError::NotConverged));
+        assert_eq!(a.norm2(), Err(MatrixError::NotConverged));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{Matrix, MatrixError};
+
+use num_traits::{Float, Num};
+
+// Upper bound on the number of Jacobi sweeps, which converge quadratically once close
+const MAX_SWEEPS: usize = 100;
+
+/// The eigendecomposition `A = VΛVᵀ` of a symmetric matrix, computed with the cyclic Jacobi
+/// method.
+///
+/// The eigenvalues are sorted in increasing order, and the columns of `V` are the matching
+/// orthonormal eigenvectors.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let a = matrix![f64; (2, 2); 2, 1; 1, 2];
+/// let eigen = a.symmetric_eigen().unwrap();
+///
+/// let values = eigen.eigenvalues();
+///
+/// assert!((values[0] - 1.0).abs() < 1e-12 && (values[1] - 3.0).abs() < 1e-12);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct SymmetricEigen<T: Num> {
+    /// The eigenvalues, in increasing order
+    eigenvalues: Vec<T>,
+
+    /// The eigenvectors, as columns
+    eigenvectors: Matrix<T>,
+}
+
+impl<T: Float> Matrix<T> {
+    /// Compute the eigenvalues and eigenvectors of a symmetric matrix.
+    /// Only the lower triangle of the matrix is read.
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::NotConverged` if the Jacobi sweeps do not converge (as for entries that are
+    /// not finite)
+    pub fn symmetric_eigen(&self) -> Result<SymmetricEigen<T>, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
+        }
+
+        let n = self.height;
+        let mut a = self.data.clone();
+        for i in 0..n {
+            for j in (i + 1)..n {
+                a[i * n + j] = a[j * n + i];
+            }
+        }
+
+        let mut v = Self::identity(n).data;
+        let scale = a.iter().fold(T::zero(), |acc, &x| acc + x * x);
+        let threshold = T::epsilon() * T::epsilon() * scale;
+        let converged = |a: &[T]| {
+            let off_diagonal = (0..n)
+                .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+                .fold(T::zero(), |acc, (i, j)| acc + a[i * n + j] * a[i * n + j]);
+            off_diagonal <= threshold
+        };
+
+        for _ in 0..MAX_SWEEPS {
+            if converged(&a) {
+                break;
+            }
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let apq = a[p * n + q];
+                    if apq.is_zero() {
+                        continue;
+                    }
+
+                    // The rotation `[c s; -s c]` in the `(p, q)` plane that zeroes `a[p][q]`
+                    let theta = (a[q * n + q] - a[p * n + p]) / (apq + apq);
+                    let t = theta.signum() / (theta.abs() + (theta * theta + T::one()).sqrt());
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+
+                    for k in 0..n {
+                        let (kp, kq) = (a[k * n + p], a[k * n + q]);
+                        a[k * n + p] = c * kp - s * kq;
+                        a[k * n + q] = s * kp + c * kq;
+                    }
+
+                    for k in 0..n {
+                        let (pk, qk) = (a[p * n + k], a[q * n + k]);
+                        a[p * n + k] = c * pk - s * qk;
+                        a[q * n + k] = s * pk + c * qk;
+                    }
+
+                    for k in 0..n {
+                        let (kp, kq) = (v[k * n + p], v[k * n + q]);
+                        v[k * n + p] = c * kp - s * kq;
+                        v[k * n + q] = s * kp + c * kq;
+                    }
+                }
+            }
+        }
+
+        if !converged(&a) {
+            return Err(MatrixError::NotConverged);
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|&i, &j| {
+            a[i * n + i]
+                .partial_cmp(&a[j * n + j])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let mut eigenvectors = Self::zeros(n);
+        for (col, &from) in order.iter().enumerate() {
+            for row in 0..n {
+                eigenvectors.data[row * n + col] = v[row * n + from];
+            }
+        }
+
+        Ok(SymmetricEigen {
+            eigenvalues: order.iter().map(|&i| a[i * n + i]).collect(),
+            eigenvectors,
+        })
+    }
+}
+
+impl<T: Float> SymmetricEigen<T> {
+    // Wraps eigenpairs computed elsewhere, such as the Ritz pairs of `lanczos`
+    pub(super) fn from_parts(eigenvalues: Vec<T>, eigenvectors: Matrix<T>) -> Self {
+        SymmetricEigen {
+            eigenvalues,
+            eigenvectors,
+        }
+    }
+
+    /// The eigenvalues, in increasing order
+    pub fn eigenvalues(&self) -> &[T] {
+        &self.eigenvalues
+    }
+
+    /// The orthonormal eigenvectors, where column `i` belongs to `eigenvalues()[i]`
+    pub fn eigenvectors(&self) -> Matrix<T> {
+        self.eigenvectors.clone()
+    }
+}
+
+#[cfg(test)]
+mod symmetric_eigen_tests {
+    use crate::linalg::test_util::assert_close;
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_decomposition() {
+        let a = Matrix::from_vec(
+            4,
+            vec![
+                4.0, -30.0, 60.0, -35.0, -30.0, 300.0, -675.0, 420.0, 60.0, -675.0, 1620.0,
+                -1050.0, -35.0, 420.0, -1050.0, 700.0,
+            ],
+        );
+        let eigen = a.symmetric_eigen().unwrap();
+        let v = eigen.eigenvectors();
+        let mut v_t = v.clone();
+        v_t.transpose();
+
+        assert_close(&(v_t.clone() * v.clone()), &Matrix::identity(4));
+        assert_close(
+            &(a.clone() * v.clone()),
+            &(v.clone() * Matrix::diag(eigen.eigenvalues().to_vec())),
+        );
+        assert!(eigen.eigenvalues().windows(2).all(|w| w[0] <= w[1]));
+
+        // Known eigenvalues of this matrix
+        let expected = [
+            0.166_642_861_171_890_5,
+            1.478_054_844_778_137,
+            37.101_491_365_127_66,
+            2_585.253_810_928_9,
+        ];
+        for (value, expected) in eigen.eigenvalues().iter().zip(&expected) {
+            assert!((value - expected).abs() / expected < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_lower_triangle() {
+        // The upper triangle is ignored
+        let a: Matrix<f64> = Matrix::from_vec(2, vec![2.0, 100.0, 1.0, 2.0]);
+        let eigen = a.symmetric_eigen().unwrap();
+
+        assert!((eigen.eigenvalues()[0] - 1.0).abs() < 1e-12);
+        assert!((eigen.eigenvalues()[1] - 3.0).abs() < 1e-12);
+        assert_eq!(
+            Matrix::<f64>::zeros((2, 3)).symmetric_eigen(),
+            Err(MatrixError::NotSquare)
+        );
+    }
+
+    #[test]
+    fn test_not_converged() {
+        let a = Matrix::from_vec(2, vec![1.0, f64::NAN, f64::NAN, 1.0]);
+
+        assert_eq!(a.symmetric_eigen(), Err(MatrixError::NotConverged));
+    }
+}
@@ -0,0 +1,24 @@
+// Assertions shared by the unit tests of the decompositions
+
+use crate::Matrix;
+
+#[cfg(feature = "complex")]
+use num_complex::Complex;
+
+// Asserts that two matrices have the same size and agree entry by entry to within rounding
+pub(crate) fn assert_close(left: &Matrix<f64>, right: &Matrix<f64>) {
+    assert_eq!(left.size(), right.size());
+    for (l, r) in left.as_slice().iter().zip(right.as_slice()) {
+        assert!((l - r).abs() < 1e-10, "{} != {}", left, right);
+    }
+}
+
+// Asserts that two lists of complex values, such as eigenvalues in the same order, agree to within
+// rounding
+#[cfg(feature = "complex")]
+pub(crate) fn assert_close_values(left: &[Complex<f64>], right: &[Complex<f64>]) {
+    assert_eq!(left.len(), right.len());
+    for (l, r) in left.iter().zip(right) {
+        assert!((l - r).norm() < 1e-9, "{:?} != {:?}", left, right);
+    }
+}
@@ -0,0 +1,73 @@
+use num_traits::Num;
+
+// Solves `L X = B` in place, where `l` is a lower triangular `n * n` matrix and `b` holds the `k`
+// right hand side columns, both stored row by row. Only the lower triangle of `l` is read, and
+// its diagonal is assumed to be all `1`s when `unit` is set.
+pub(crate) fn forward_substitute<T: Num + Copy>(
+    l: &[T],
+    n: usize,
+    b: &mut [T],
+    k: usize,
+    unit: bool,
+) {
+    for i in 0..n {
+        for j in 0..i {
+            let factor = l[i * n + j];
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] - factor * b[j * k + c];
+            }
+        }
+
+        if !unit {
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] / l[i * n + i];
+            }
+        }
+    }
+}
+
+// Solves `U X = B` in place, where `u` is an upper triangular `n * n` matrix and `b` holds the `k`
+// right hand side columns, both stored row by row. Only the upper triangle of `u` is read, and
+// its diagonal is assumed to be all `1`s when `unit` is set.
+pub(crate) fn back_substitute<T: Num + Copy>(u: &[T], n: usize, b: &mut [T], k: usize, unit: bool) {
+    for i in (0..n).rev() {
+        for j in (i + 1)..n {
+            let factor = u[i * n + j];
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] - factor * b[j * k + c];
+            }
+        }
+
+        if !unit {
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] / u[i * n + i];
+            }
+        }
+    }
+}
+
+// Solves `Lᵀ X = B` in place, where `l` is a lower triangular `n * n` matrix and `b` holds the `k`
+// right hand side columns, both stored row by row. Only the lower triangle of `l` is read, and
+// its diagonal is assumed to be all `1`s when `unit` is set.
+pub(crate) fn back_substitute_transpose<T: Num + Copy>(
+    l: &[T],
+    n: usize,
+    b: &mut [T],
+    k: usize,
+    unit: bool,
+) {
+    for i in (0..n).rev() {
+        for j in (i + 1)..n {
+            let factor = l[j * n + i];
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] - factor * b[j * k + c];
+            }
+        }
+
+        if !unit {
+            for c in 0..k {
+                b[i * k + c] = b[i * k + c] / l[i * n + i];
+            }
+        }
+    }
+}
@@ -9,6 +9,13 @@
 /// let mat_data: Vec<Vec<i32>> = vec![Vec::with_capacity(2); 2];
 /// let matrix3 = matrix![mat_data];
 /// ```
+/// # Panics
+/// The `Vec` form panics if the rows do not all have the same length
+/// ```should_panic
+/// #[macro_use] extern crate mtrs;
+///
+/// let ragged = matrix![vec![vec![1, 2], vec![3]]];
+/// ```
 #[macro_export]
 macro_rules! matrix {
     // matrix![(2, 2); 1, 2; 3, 4]
@@ -23,15 +30,7 @@ macro_rules! matrix {
 
     // matrix![vec![vec![1, 2], vec![3, 4]]]
     ($vec:expr) => {
-        mtrs::Matrix::from_vec(($vec.len(), $vec[0].len()), {
-            let mut vec = Vec::new();
-            for row in $vec {
-                for entry in row {
-                    vec.push(entry);
-                }
-            }
-
-            vec
-        })
+        <mtrs::Matrix<_> as std::convert::TryFrom<Vec<Vec<_>>>>::try_from($vec)
+            .unwrap_or_else(|err| panic!("{}", err))
     };
 }
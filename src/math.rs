@@ -1,33 +1,271 @@
-use crate::Matrix;
+use crate::determinant::Determinant;
+use crate::gemm;
+use crate::inverse::Inverse;
+use crate::parallel::{self, Element};
+use crate::{Matrix, MatrixError, MatrixView, MatrixViewMut};
 
 use num_traits::Num;
 
-impl<T: Num + Clone + Copy> Matrix<T> {
+impl<T: Num + Clone + Copy + Element> Matrix<T> {
     /// Transposes the matrix, via mutating the original data.
     /// Does not return a new struct, instead modifies the old one.
+    ///
+    /// Nothing is allocated: square matrices swap entries across the diagonal a tile at a time,
+    /// and other shapes move each entry along the cycles of the transposition permutation.
     /// ```
     /// #[macro_use] extern crate mtrs;
     ///
     /// let mut matrix = matrix![(2, 2); 1, 2; 3, 4];
     /// matrix.transpose();
     /// assert_eq!(matrix, matrix![(2, 2); 1, 3; 2, 4]);
+    ///
+    /// let mut matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// matrix.transpose();
+    /// assert_eq!(matrix, matrix![(3, 2); 1, 4; 2, 5; 3, 6]);
     /// ```
     pub fn transpose(&mut self) {
-        let v = self.as_vec();
-        let mut transposed = vec![Vec::with_capacity(v.len()); v[0].len()];
+        let (height, width) = self.size();
+        if height == width {
+            transpose_square(&mut self.data, height);
+        } else if height > 1 && width > 1 {
+            transpose_cycles(&mut self.data, height, width);
+        }
+
+        self.height = width;
+        self.width = height;
+    }
+
+    /// Returns the transpose of the matrix as a new matrix, leaving the original untouched. The
+    /// entries are copied in bands of rows of the result, reading the source a row at a time, so
+    /// that both sides are accessed in cache friendly order.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    ///
+    /// assert_eq!(matrix.transposed(), matrix![(3, 2); 1, 4; 2, 5; 3, 6]);
+    /// assert_eq!(matrix.transposed().transposed(), matrix);
+    /// ```
+    pub fn transposed(&self) -> Self {
+        let (height, width) = self.size();
+        let mut data = vec![T::zero(); height * width];
+        let source = &self.data;
 
-        for i in 0..v[0].len() {
-            for row in &v {
-                transposed[i].push(row[i]);
+        parallel::for_each_chunk(&mut data, TRANSPOSE_BLOCK * height, |band, rows| {
+            let (first, cols) = (band * TRANSPOSE_BLOCK, rows.len() / height);
+            for (row, source_row) in source.chunks(width).enumerate() {
+                for (offset, &value) in source_row[first..first + cols].iter().enumerate() {
+                    rows[offset * height + row] = value;
+                }
             }
+        });
+
+        Self::from_vec((width, height), data)
+    }
+
+    /// Add two matrices of the same size, entry by entry
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::{Matrix, MatrixError};
+    ///
+    /// let m1 = matrix![(2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(m1.try_add(&Matrix::identity(2)), Ok(matrix![(2, 2); 2, 2; 3, 5]));
+    /// assert_eq!(
+    ///     m1.try_add(&Matrix::identity(3)),
+    ///     Err(MatrixError::DimensionMismatch { left: (2, 2), right: (3, 3) })
+    /// );
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_with(other, |left, right| left + right)
+    }
+
+    /// Subtract a matrix of the same size, entry by entry
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let m1 = matrix![(2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(m1.try_sub(&Matrix::identity(2)), Ok(matrix![(2, 2); 0, 2; 3, 3]));
+    /// assert!(m1.try_sub(&Matrix::identity(1)).is_err());
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_sub(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.zip_with(other, |left, right| left - right)
+    }
+
+    /// Multiply two matrices, where the width of `self` must equal the height of `other`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::MatrixError;
+    ///
+    /// let m1 = matrix![(2, 3); 2, 3, 4; 1, 0, 0];
+    /// let m2 = matrix![(3, 2); 0, 1000; 1, 100; 0, 10];
+    ///
+    /// assert_eq!(m1.try_mul(&m2), Ok(matrix![(2, 2); 3, 2340; 0, 1000]));
+    /// assert_eq!(
+    ///     m1.try_mul(&m1),
+    ///     Err(MatrixError::DimensionMismatch { left: (2, 3), right: (2, 3) })
+    /// );
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
         }
 
-        self.data = transposed
-            .iter()
-            .flat_map(|row| row.iter().copied())
-            .collect();
-        self.height = transposed[0].len();
-        self.width = transposed.len();
+        let mut product = Self::zeros((self.height, other.width));
+        gemm::kernel(
+            T::one(),
+            &self.as_view(),
+            &other.as_view(),
+            T::zero(),
+            &mut product.as_view_mut(),
+        );
+
+        Ok(product)
+    }
+
+    /// Add a matrix of the same size to this one in place, entry by entry
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let mut m1 = matrix![(2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(m1.try_add_assign(&Matrix::identity(2)), Ok(()));
+    /// assert_eq!(m1, matrix![(2, 2); 2, 2; 3, 5]);
+    /// assert!(m1.try_add_assign(&Matrix::identity(3)).is_err());
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ, leaving `self` unchanged
+    pub fn try_add_assign(&mut self, other: &Self) -> Result<(), MatrixError> {
+        self.zip_assign(other, |left, right| left + right)
+    }
+
+    /// Subtract a matrix of the same size from this one in place, entry by entry
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let mut m1 = matrix![(2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(m1.try_sub_assign(&Matrix::identity(2)), Ok(()));
+    /// assert_eq!(m1, matrix![(2, 2); 0, 2; 3, 3]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ, leaving `self` unchanged
+    pub fn try_sub_assign(&mut self, other: &Self) -> Result<(), MatrixError> {
+        self.zip_assign(other, |left, right| left - right)
+    }
+
+    /// Replace the matrix with its product with `other`, reusing the existing storage. The product
+    /// is computed by the same kernel as `Matrix::try_mul`, one band of rows at a time, so only a
+    /// band of scratch space is allocated and the storage only grows when `other` is wider than it
+    /// is tall.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let mut m1 = matrix![(2, 3); 2, 3, 4; 1, 0, 0];
+    ///
+    /// assert_eq!(m1.try_mul_assign(&matrix![(3, 2); 0, 1000; 1, 100; 0, 10]), Ok(()));
+    /// assert_eq!(m1, matrix![(2, 2); 3, 2340; 0, 1000]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ, leaving `self`
+    /// unchanged
+    pub fn try_mul_assign(&mut self, other: &Self) -> Result<(), MatrixError> {
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let (height, old_width, new_width) = (self.height, self.width, other.width);
+        let band = gemm::BAND_ROWS.min(height).max(1);
+        let mut scratch = vec![T::zero(); band * old_width];
+
+        // Each band of product rows only depends on the same rows of `self`, which are copied out
+        // first. Narrowing bands are written front to back and widening bands back to front, so no
+        // row is overwritten before it is read.
+        let multiply_band = |data: &mut [T], start: usize, scratch: &mut [T]| {
+            let rows = band.min(height - start);
+            let scratch = &mut scratch[..rows * old_width];
+            scratch.copy_from_slice(&data[start * old_width..(start + rows) * old_width]);
+            gemm::kernel(
+                T::one(),
+                &MatrixView::from_row_major(scratch, (rows, old_width)),
+                &other.as_view(),
+                T::zero(),
+                &mut MatrixViewMut::from_row_major(
+                    &mut data[start * new_width..(start + rows) * new_width],
+                    (rows, new_width),
+                ),
+            );
+        };
+
+        if new_width <= old_width {
+            for start in (0..height).step_by(band) {
+                multiply_band(&mut self.data, start, &mut scratch);
+            }
+            self.data.truncate(height * new_width);
+        } else {
+            self.data.resize(height * new_width, T::zero());
+            for start in (0..height).step_by(band).rev() {
+                multiply_band(&mut self.data, start, &mut scratch);
+            }
+        }
+
+        self.width = new_width;
+        Ok(())
+    }
+
+    // Combines a matrix of the same size into this one entry by entry, in place
+    fn zip_assign<F: Fn(T, T) -> T + Element>(
+        &mut self,
+        other: &Self,
+        f: F,
+    ) -> Result<(), MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        parallel::zip_mut(&mut self.data, &other.data, |left, right| {
+            *left = f(*left, right)
+        });
+        Ok(())
+    }
+
+    // Combines two matrices of the same size entry by entry
+    fn zip_with<F: Fn(T, T) -> T + Element>(
+        &self,
+        other: &Self,
+        f: F,
+    ) -> Result<Self, MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let (left, right) = (&self.data, &other.data);
+        Ok(Self::from_vec(
+            self.size(),
+            parallel::tabulate(left.len(), |i| f(left[i], right[i])),
+        ))
     }
 
     /// Add a scalar constant to the matrix
@@ -40,7 +278,11 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(m1.scalar_add(2), matrix![(2, 2); 3, 2; 2, 3]);
     /// ```
     pub fn scalar_add(&self, value: T) -> Self {
-        Self::from_vec(self.size(), self.data.iter().map(|x| *x + value).collect())
+        let data = &self.data;
+        Self::from_vec(
+            self.size(),
+            parallel::tabulate(data.len(), |i| data[i] + value),
+        )
     }
 
     /// Subtract a scalar constant from the matrix
@@ -53,7 +295,11 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(m1.scalar_sub(2), matrix![(2, 2); -1, -2; -2, -1]);
     /// ```
     pub fn scalar_sub(&self, value: T) -> Self {
-        Self::from_vec(self.size(), self.data.iter().map(|x| *x - value).collect())
+        let data = &self.data;
+        Self::from_vec(
+            self.size(),
+            parallel::tabulate(data.len(), |i| data[i] - value),
+        )
     }
 
     /// Multiply a scalar constant with the matrix
@@ -66,7 +312,11 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(m1.scalar_mul(3), matrix![(2, 2); 3, 0; 0, 3]);
     /// ```
     pub fn scalar_mul(&self, value: T) -> Self {
-        Self::from_vec(self.size(), self.data.iter().map(|x| *x * value).collect())
+        let data = &self.data;
+        Self::from_vec(
+            self.size(),
+            parallel::tabulate(data.len(), |i| data[i] * value),
+        )
     }
 
     /// Divide each entry in the matrix by a scalar constant
@@ -79,10 +329,58 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(m1.scalar_div(2), matrix![(2, 2); 2, 3; 4, 5]);
     /// ```
     pub fn scalar_div(&self, value: T) -> Self {
-        Self::from_vec(self.size(), self.data.iter().map(|x| *x / value).collect())
+        let data = &self.data;
+        Self::from_vec(
+            self.size(),
+            parallel::tabulate(data.len(), |i| data[i] / value),
+        )
+    }
+}
+
+// The side of the tiles that square transposition swaps, and the number of rows of the result
+// that `Matrix::transposed` fills at once
+const TRANSPOSE_BLOCK: usize = 32;
+
+// Transposes an `n * n` matrix in place, swapping each tile above the diagonal with its mirror
+fn transpose_square<T>(data: &mut [T], n: usize) {
+    for tile_row in (0..n).step_by(TRANSPOSE_BLOCK) {
+        for tile_col in (tile_row..n).step_by(TRANSPOSE_BLOCK) {
+            for row in tile_row..(tile_row + TRANSPOSE_BLOCK).min(n) {
+                for col in (row + 1).max(tile_col)..(tile_col + TRANSPOSE_BLOCK).min(n) {
+                    data.swap(row * n + col, col * n + row);
+                }
+            }
+        }
     }
+}
 
-    /// Calculate the determinant of the `Matrix` (if the `Matrix` is square)
+// Transposes a `height * width` matrix in place. The entry at index `i` belongs at
+// `i * height mod (len - 1)`, which splits the indices into cycles. Each cycle is rotated once,
+// starting from its smallest index, which is found by walking the cycle without storing anything.
+fn transpose_cycles<T>(data: &mut [T], height: usize, width: usize) {
+    let last = height * width - 1;
+    let target = |i: usize| i * height % last;
+
+    for start in 1..last {
+        let mut next = target(start);
+        while next > start {
+            next = target(next);
+        }
+
+        if next == start {
+            let mut index = target(start);
+            while index != start {
+                data.swap(start, index);
+                index = target(index);
+            }
+        }
+    }
+}
+
+impl<T: Determinant> Matrix<T> {
+    /// Calculate the determinant of the `Matrix` (if the `Matrix` is square).
+    /// Integer determinants are exact, as long as no intermediate value overflows. Unsigned
+    /// integers check for overflow, which includes negative intermediate values.
     /// ```
     /// #[macro_use] extern crate mtrs;
     ///
@@ -91,79 +389,75 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(matrix.determinant(), Some(30));
     /// ```
     /// # Failure
-    /// Fails if the matrix is not square
+    /// Fails if the matrix is not square, or if an unsigned intermediate value overflows
     pub fn determinant(&self) -> Option<T> {
+        self.try_determinant().ok()
+    }
+
+    /// Calculate the determinant of the `Matrix`, or the reason it could not be calculated
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::MatrixError;
+    ///
+    /// assert_eq!(matrix![(2, 2); 1, 2; 3, 4].try_determinant(), Ok(-2));
+    /// assert_eq!(matrix![(1, 2); 1, 2].try_determinant(), Err(MatrixError::NotSquare));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::Overflow` if an unsigned intermediate value overflows
+    pub fn try_determinant(&self) -> Result<T, MatrixError> {
         if self.height != self.width {
-            return None;
+            return Err(MatrixError::NotSquare);
         }
 
-        let mut det = T::one();
-        let mut total = T::one();
-
-        let mut temp = vec![T::zero(); self.height + 1];
-        let mut mat = self.data.clone();
-
-        for i in 0..self.height {
-            let mut index = i;
-
-            while index < self.width && mat[index * self.width + i] == T::zero() {
-                index += 1;
-            }
-
-            if index == self.height {
-                continue;
-            }
-
-            if index != i {
-                for j in 0..self.height {
-                    mat.swap(index * self.width + j, i * self.width + j);
-                }
-
-                if (index - i) % 2 != 0 {
-                    // Negate det
-                    det = T::zero() - det;
-                }
-            }
-
-            for j in 0..self.height {
-                temp[j] = mat[i * self.width + j];
-            }
-
-            for j in (i + 1)..self.height {
-                let diag = temp[i];
-                let row = mat[j * self.width + i];
-
-                for k in 0..self.height {
-                    mat[j * self.width + k] = (diag * mat[j * self.width + k]) - (row * temp[k]);
-                }
-
-                total = total * diag;
-            }
-        }
+        T::determinant(&mut self.data.clone(), self.height).ok_or(MatrixError::Overflow)
+    }
 
-        for i in 0..self.height {
-            det = det * mat[i * self.width + i];
+    /// Calculate the determinant of the `Matrix`, reporting overflow instead of returning a
+    /// wrong value
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::MatrixError;
+    ///
+    /// let matrix = matrix![i32; (2, 2); 100000, 1; 1, 100000];
+    ///
+    /// assert_eq!(matrix.checked_determinant(), Err(MatrixError::Overflow));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::Overflow` if an intermediate value overflows (or, for floats, the result
+    /// is not finite)
+    pub fn checked_determinant(&self) -> Result<T, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
         }
 
-        Some(det / total)
+        T::checked_determinant(&mut self.data.clone(), self.height).ok_or(MatrixError::Overflow)
     }
+}
 
-    /// Calculate the inverse of `Matrix<T>`, via multiplying the reciprocal of the `determinant`
+impl<T: Inverse> Matrix<T> {
+    /// Calculate the inverse of `Matrix<T>`, via Gauss-Jordan elimination. Floats pivot on the
+    /// largest entry of each column, while exact types such as integers are eliminated without
+    /// fractions, see `Inverse`.
     /// ```
     /// #[macro_use] extern crate mtrs;
     ///
     /// let matrix = matrix![f32; (2, 2); -1, 1.5; 1, -1];
     ///
-    /// assert_eq!(matrix.inverse().expect("Could not take inverse"), matrix![f32; (2, 2); 2, -3; -2, 2]);
+    /// assert_eq!(matrix.inverse().expect("Could not take inverse"), matrix![f32; (2, 2); 2, 3; 2, 2]);
+    /// assert_eq!(matrix![(2, 2); 2, 1; 1, 1].inverse(), Ok(matrix![(2, 2); 1, -1; -1, 2]));
     /// ```
     /// # Failure
-    /// Fails if the matrix is not invertible (that is, it is not square __or__ the determinant is `0`)
-    pub fn inverse(&self) -> Option<Self> {
-        let det = self.determinant()?;
-        if det.is_zero() {
-            None
-        } else {
-            Some(self.scalar_div(det))
+    /// Fails with `MatrixError::NotSquare` if the matrix is not square, and with
+    /// `MatrixError::Singular` if it has no inverse in the element type. For floats, this includes
+    /// a pivot that is negligibly small compared to the largest entry of the matrix. Integers fail
+    /// with `MatrixError::Overflow` if an intermediate value overflows.
+    pub fn inverse(&self) -> Result<Self, MatrixError> {
+        if self.height != self.width {
+            return Err(MatrixError::NotSquare);
         }
+
+        T::inverse(&mut self.data.clone(), self.height).map(|inv| Self::from_vec(self.height, inv))
     }
 }
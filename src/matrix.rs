@@ -1,7 +1,7 @@
-use crate::size::Size;
-use crate::Matrix;
+use std::marker::PhantomData;
 
-use std::io;
+use crate::size::{Size, SliceRange};
+use crate::{Layout, Matrix, MatrixError};
 
 use num_traits::Num;
 
@@ -12,6 +12,7 @@ macro_rules! new {
             height: $height,
             width: $width,
             data: $body,
+            layout: PhantomData,
         }
     };
 }
@@ -40,9 +41,34 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// ```
     pub fn from_vec<S: Size>(size: S, body: Vec<T>) -> Self {
         let (height, width) = size.dim();
+        debug_assert_eq!(body.len(), height * width, "Invalid length for matrix body");
         new!(height, width, body)
     }
 
+    /// Creates a new matrix from a pre-given size, checking that the body has the right length
+    /// ```
+    /// use mtrs::{Matrix, MatrixError};
+    ///
+    /// let matrix = Matrix::try_from_vec((2, 2), vec![1, 2, 7, 6]);
+    /// assert_eq!(matrix, Ok(Matrix::from_vec(2, vec![1, 2, 7, 6])));
+    ///
+    /// let matrix = Matrix::try_from_vec((3, 3), vec![1, 2]);
+    /// assert_eq!(matrix, Err(MatrixError::InvalidLength { expected: 9, found: 2 }));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if the length of `body` is not `height * width`
+    pub fn try_from_vec<S: Size>(size: S, body: Vec<T>) -> Result<Self, MatrixError> {
+        let (height, width) = size.dim();
+        if body.len() != height * width {
+            Err(MatrixError::InvalidLength {
+                expected: height * width,
+                found: body.len(),
+            })
+        } else {
+            Ok(new!(height, width, body))
+        }
+    }
+
     /// Creates a new matrix from a slice
     /// ```
     /// use mtrs::Matrix;
@@ -54,9 +80,43 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// ```
     pub fn from_slice<S: Size>(size: S, body: &[T]) -> Self {
         let (height, width) = size.dim();
+        debug_assert_eq!(body.len(), height * width, "Invalid length for matrix body");
         new!(height, width, body.to_vec())
     }
 
+    /// Creates a new matrix from a slice, checking that the slice has the right length
+    /// ```
+    /// use mtrs::Matrix;
+    ///
+    /// assert!(Matrix::try_from_slice((2, 2), &[1, 2, 7, 6]).is_ok());
+    /// assert!(Matrix::try_from_slice((2, 2), &[1, 2, 7]).is_err());
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if the length of `body` is not `height * width`
+    pub fn try_from_slice<S: Size>(size: S, body: &[T]) -> Result<Self, MatrixError> {
+        Self::try_from_vec(size, body.to_vec())
+    }
+
+    /// Creates a new matrix of the given size from the entries of an iterator, in row-major order
+    /// ```
+    /// use mtrs::{Matrix, MatrixError};
+    ///
+    /// let matrix = Matrix::from_iter_shape((2, 3), 1..7);
+    /// assert_eq!(matrix, Ok(Matrix::from_vec((2, 3), vec![1, 2, 3, 4, 5, 6])));
+    ///
+    /// let matrix = Matrix::from_iter_shape(2, 1..7);
+    /// assert_eq!(matrix, Err(MatrixError::InvalidLength { expected: 4, found: 6 }));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if the iterator does not yield exactly
+    /// `height * width` entries
+    pub fn from_iter_shape<S: Size, I: IntoIterator<Item = T>>(
+        size: S,
+        iter: I,
+    ) -> Result<Self, MatrixError> {
+        Self::try_from_vec(size, iter.into_iter().collect())
+    }
+
     /// Create a `Matrix<T>` of size `M * N` filled with `0`s
     /// ```
     /// use mtrs::Matrix;
@@ -103,37 +163,6 @@ impl<T: Num + Clone + Copy> Matrix<T> {
         m
     }
 
-    /// Returns a tuple representing the dimensions (`(height, width)`)
-    /// ```
-    /// use mtrs::Matrix;
-    ///
-    /// let matrix: Matrix<i32> = Matrix::ones((2, 3));
-    /// assert_eq!(matrix.size(), (2, 3));
-    /// ```
-    pub fn size(&self) -> (usize, usize) {
-        (self.height, self.width)
-    }
-
-    /// Wrapper function for `self.data.as_slice()`
-    pub fn as_slice(&self) -> &[T] {
-        self.data.as_slice()
-    }
-
-    /// Wrapper function for `self.data.as_mut_slice()`
-    pub fn as_mut_slice(&mut self) -> &mut [T] {
-        self.data.as_mut_slice()
-    }
-
-    /// Wrapper function for `self.data.as_ptr()`
-    pub fn as_ptr(&self) -> *const T {
-        self.data.as_ptr()
-    }
-
-    /// Wrapper function for `self.data.as_mut_ptr()`
-    pub fn as_mut_ptr(&mut self) -> *mut T {
-        self.data.as_mut_ptr()
-    }
-
     /// Zero out the matrix
     /// # Safety
     /// Only use when the values inside the matrix can be safely zeroed
@@ -150,19 +179,7 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(matrix.as_vec(), vec![vec![2, 1], vec![4, 3]]);
     /// ```
     pub fn as_vec(&self) -> Vec<Vec<T>> {
-        let mut body = Vec::new();
-        let sliced = self.as_slice();
-
-        for row in 0..self.height {
-            let mut row_vec = Vec::new();
-            for col in 0..self.width {
-                row_vec.push(sliced[row * self.width + col]);
-            }
-
-            body.push(row_vec);
-        }
-
-        body
+        self.rows().map(<[T]>::to_vec).collect()
     }
 
     /// Returns a single column of the Matrix
@@ -177,18 +194,9 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(mat.get_col(3), None)
     /// ```
     pub fn get_col(&self, index: usize) -> Option<Vec<T>> {
-        if index >= self.width {
-            None
-        } else {
-            let mut body = Vec::new();
-            let sliced = self.as_slice();
-
-            for row in 0..self.height {
-                body.push(sliced[row * self.width + index]);
-            }
-
-            Some(body)
-        }
+        self.cols_iter()
+            .nth(index)
+            .map(|col| col.copied().collect())
     }
 
     /// Returns a `Vec` of all the columns
@@ -201,50 +209,78 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     /// assert_eq!(mat.cols(), vec![vec![1, 3, 5], vec![2, 4, 6]]);
     /// ```
     pub fn cols(&self) -> Vec<Vec<T>> {
-        let mut body = Vec::new();
-
-        for i in 0..self.width {
-            // We can call `unwrap` here as it is guaranteed to be within bounds
-            body.push(self.get_col(i).unwrap());
-        }
+        self.cols_iter().map(|col| col.copied().collect()).collect()
+    }
 
-        body
+    /// Copy the block of the matrix made of the given rows and columns into a new matrix. Each
+    /// of `rows` and `cols` can be a range (including `..`) or a single index.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let mat = matrix![(3, 3); 1, 2, 3; 4, 5, 6; 7, 8, 9];
+    ///
+    /// assert_eq!(mat.slice(1..3, ..), matrix![(2, 3); 4, 5, 6; 7, 8, 9]);
+    /// assert_eq!(mat.slice(..=1, 2), matrix![(2, 1); 3; 6]);
+    /// ```
+    /// # Panics
+    /// Panics if the block does not lie inside the matrix, see `Matrix::get_block` for a
+    /// non-panicking version
+    pub fn slice<R: SliceRange, C: SliceRange>(&self, rows: R, cols: C) -> Self {
+        self.view(rows, cols).to_matrix()
     }
 
-    /// Returns an entry in the Matrix safely, that is:
+    /// Copy the block of the given size, whose top left entry is at `start`, into a new matrix
     /// ```
-    /// extern crate mtrs;
-    /// use mtrs::matrix;
+    /// #[macro_use] extern crate mtrs;
     ///
-    /// let mat = matrix![i32; (2, 2); 1, 2; 3, 4];
+    /// let mat = matrix![(3, 3); 1, 2, 3; 4, 5, 6; 7, 8, 9];
     ///
-    /// assert_eq!(mat.get((0, 1)), Some(&2));
+    /// assert_eq!(mat.submatrix((0, 1), (2, 2)), matrix![(2, 2); 2, 3; 5, 6]);
     /// ```
-    /// # Failure
-    /// Fails if the location is out of bounds
-    pub fn get<S: Size>(&self, loc: S) -> Option<&T> {
-        let (h, w) = loc.dim();
-        self.data.get(h * self.width + w)
+    /// # Panics
+    /// Panics if the block does not lie inside the matrix
+    pub fn submatrix<P: Size, S: Size>(&self, start: P, size: S) -> Self {
+        let ((row, col), (height, width)) = (start.dim(), size.dim());
+        match (row.checked_add(height), col.checked_add(width)) {
+            (Some(bottom), Some(right)) => self.slice(row..bottom, col..right),
+            _ => panic!(
+                "the block is out of bounds for a {}x{} matrix",
+                self.height, self.width
+            ),
+        }
     }
 
-    /// Sets an entry in the Matrix
+    /// Overwrite the block of the matrix whose top left entry is at `start` with `block`
     /// ```
     /// #[macro_use] extern crate mtrs;
-    /// use mtrs::Matrix;
+    /// use mtrs::{Matrix, MatrixError};
     ///
-    /// let mut mat = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
-    /// mat.set(1, 13);
-    /// assert_eq!(mat.as_slice(), &[1, 2, 3, 4, 13, 6]);
+    /// let mut mat: Matrix<i32> = Matrix::zeros((2, 3));
+    /// mat.set_block((0, 1), &matrix![(2, 2); 1, 2; 3, 4]).unwrap();
+    ///
+    /// assert_eq!(mat, matrix![(2, 3); 0, 1, 2; 0, 3, 4]);
+    /// assert_eq!(
+    ///     mat.set_block((1, 0), &Matrix::ones(2)),
+    ///     Err(MatrixError::OutOfBounds { index: (2, 1), size: (2, 3) })
+    /// );
     /// ```
     /// # Failure
-    /// Fails if you attempt to set a value that is out of bounds
-    pub fn set<S: Size>(&mut self, loc: S, val: T) -> io::Result<()> {
-        let (h, w) = loc.dim();
-        if h >= self.height || w >= self.width {
-            Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid bounds"))
-        } else {
-            self.data[h * self.width + w] = val;
-            Ok(())
+    /// Fails with `MatrixError::OutOfBounds` if the block does not fit inside the matrix, naming
+    /// the location its bottom right entry would have
+    pub fn set_block<P: Size>(&mut self, start: P, block: &Self) -> Result<(), MatrixError> {
+        let (row, col) = start.dim();
+        let (height, width) = block.size();
+        match (row.checked_add(height), col.checked_add(width)) {
+            (Some(bottom), Some(right)) if bottom <= self.height && right <= self.width => self
+                .view_mut(row..bottom, col..right)
+                .copy_from(&block.as_view()),
+            _ => Err(MatrixError::OutOfBounds {
+                index: (
+                    row.saturating_add(height.saturating_sub(1)),
+                    col.saturating_add(width.saturating_sub(1)),
+                ),
+                size: self.size(),
+            }),
         }
     }
 
@@ -284,9 +320,117 @@ impl<T: Num + Clone + Copy> Matrix<T> {
     }
 }
 
+impl<T: Num + Clone + Copy, L: Layout> Matrix<T, L> {
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    /// ```
+    /// use mtrs::Matrix;
+    ///
+    /// let matrix: Matrix<i32> = Matrix::ones((2, 3));
+    /// assert_eq!(matrix.size(), (2, 3));
+    /// ```
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Wrapper function for `self.data.as_slice()`, which lists the entries in the order of the
+    /// layout
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Wrapper function for `self.data.as_mut_slice()`
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_mut_slice()
+    }
+
+    /// Wrapper function for `self.data.as_ptr()`
+    pub fn as_ptr(&self) -> *const T {
+        self.data.as_ptr()
+    }
+
+    /// Wrapper function for `self.data.as_mut_ptr()`
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_mut_ptr()
+    }
+
+    /// Returns an entry in the Matrix safely, that is:
+    /// ```
+    /// extern crate mtrs;
+    /// use mtrs::matrix;
+    ///
+    /// let mat = matrix![i32; (2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(mat.get((0, 1)), Some(&2));
+    /// assert_eq!(mat.get((0, 2)), None);
+    /// ```
+    /// # Failure
+    /// Fails if the location is out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<&T> {
+        self.try_get(loc).ok()
+    }
+
+    /// Returns an entry in the Matrix, or the reason it could not be accessed
+    /// ```
+    /// extern crate mtrs;
+    /// use mtrs::{matrix, MatrixError};
+    ///
+    /// let mat = matrix![i32; (2, 2); 1, 2; 3, 4];
+    ///
+    /// assert_eq!(mat.try_get((1, 0)), Ok(&3));
+    /// assert_eq!(
+    ///     mat.try_get((1, 2)),
+    ///     Err(MatrixError::OutOfBounds { index: (1, 2), size: (2, 2) })
+    /// );
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::OutOfBounds` if the location is out of bounds
+    pub fn try_get<S: Size>(&self, loc: S) -> Result<&T, MatrixError> {
+        let (h, w) = self.check_bounds(loc)?;
+        Ok(&self.data[self.offset(h, w)])
+    }
+
+    /// Sets an entry in the Matrix
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let mut mat = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// mat.set(1, 13);
+    /// assert_eq!(mat.as_slice(), &[1, 2, 3, 4, 13, 6]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::OutOfBounds` if you attempt to set a value that is out of bounds
+    pub fn set<S: Size>(&mut self, loc: S, val: T) -> Result<(), MatrixError> {
+        let (h, w) = self.check_bounds(loc)?;
+        let index = self.offset(h, w);
+        self.data[index] = val;
+        Ok(())
+    }
+
+    // Validates a location, returning it as a `(row, col)` pair
+    fn check_bounds<S: Size>(&self, loc: S) -> Result<(usize, usize), MatrixError> {
+        let (h, w) = loc.dim();
+        if h >= self.height || w >= self.width {
+            Err(MatrixError::OutOfBounds {
+                index: (h, w),
+                size: self.size(),
+            })
+        } else {
+            Ok((h, w))
+        }
+    }
+
+    // The index in `data` of entry `(row, col)`
+    pub(crate) fn offset(&self, row: usize, col: usize) -> usize {
+        let (row_stride, col_stride) = L::strides(self.height, self.width);
+        row * row_stride + col * col_stride
+    }
+}
+
 #[cfg(test)]
 mod matrix_tests {
     use super::Matrix;
+    use crate::MatrixError;
 
     #[test]
     fn test_cols() {
@@ -349,14 +493,115 @@ mod matrix_tests {
         assert_eq!(matrix.as_slice(), &[1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
     }
 
+    #[test]
+    fn test_validated_constructors() {
+        assert_eq!(
+            Matrix::try_from_vec((2, 3), vec![1, 2, 3, 4, 5]),
+            Err(MatrixError::InvalidLength {
+                expected: 6,
+                found: 5
+            })
+        );
+        assert_eq!(
+            Matrix::try_from_slice((3, 1), &[1, 2, 3]).map(|m| m.size()),
+            Ok((3, 1))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(debug_assertions)]
+    fn test_unchecked_invalid_length() {
+        let _ = Matrix::from_vec((3, 3), vec![1, 2]);
+    }
+
     #[test]
     fn test_get_set() {
         let mut matrix: Matrix<i32> = Matrix::identity(3);
 
-        assert!(matrix.set(5, 3).is_err());
+        assert_eq!(
+            matrix.set(5, 3),
+            Err(MatrixError::OutOfBounds {
+                index: (5, 5),
+                size: (3, 3)
+            })
+        );
         assert!(matrix.set(1, 2).is_ok());
         assert_eq!(matrix.get(1), Some(&2));
         assert_eq!(matrix.get(0), Some(&1));
         assert_eq!(matrix.get((1, 2)), Some(&0));
+        assert_eq!(matrix.get((0, 3)), None);
+        assert_eq!(matrix.try_get((2, 2)), Ok(&1));
+    }
+
+    #[test]
+    fn test_blocks() {
+        let matrix = Matrix::from_vec((3, 4), (1..=12).collect::<Vec<i32>>());
+
+        assert_eq!(matrix.slice(.., ..), matrix);
+        assert_eq!(
+            matrix.slice(1..=2, 3..),
+            Matrix::from_vec((2, 1), vec![8, 12])
+        );
+        assert_eq!(
+            matrix.slice(..1, ..=1),
+            Matrix::from_vec((1, 2), vec![1, 2])
+        );
+        assert_eq!(matrix.slice(2, 1..1), Matrix::zeros((1, 0)));
+        assert_eq!(matrix.submatrix((1, 1), (2, 3)), matrix.slice(1.., 1..));
+        assert!(matrix.get_block(0..4, ..).is_none());
+        assert!(matrix.get_block(5, ..).is_none());
+
+        let mut target: Matrix<i32> = Matrix::zeros((3, 4));
+        target.set_block((1, 2), &matrix.submatrix(0, 2)).unwrap();
+        assert_eq!(
+            target,
+            Matrix::from_vec((3, 4), vec![0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 5, 6])
+        );
+        assert!(target.set_block(0, &matrix).is_ok());
+        assert_eq!(target, matrix);
+        assert_eq!(
+            target.set_block((0, 1), &matrix),
+            Err(MatrixError::OutOfBounds {
+                index: (2, 4),
+                size: (3, 4)
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the block is out of bounds for a 2x2 matrix")]
+    fn test_slice_out_of_bounds() {
+        Matrix::<i32>::identity(2).slice(1..3, ..);
+    }
+
+    #[test]
+    fn test_block_overflow() {
+        let mut matrix = Matrix::<i32>::identity(2);
+        let block = Matrix::ones((1, 2));
+
+        assert!(matrix.get_block(usize::MAX, ..).is_none());
+        assert!(matrix.get_block(.., 1..=usize::MAX).is_none());
+        assert!(matrix.get_block(..=usize::MAX, 0).is_none());
+        assert_eq!(
+            matrix.set_block((usize::MAX, 0), &Matrix::identity(1)),
+            Err(MatrixError::OutOfBounds {
+                index: (usize::MAX, 0),
+                size: (2, 2)
+            })
+        );
+        assert_eq!(
+            matrix.set_block((0, usize::MAX), &block),
+            Err(MatrixError::OutOfBounds {
+                index: (0, usize::MAX),
+                size: (2, 2)
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the block is out of bounds for a 2x2 matrix")]
+    fn test_submatrix_overflow() {
+        Matrix::<i32>::identity(2).submatrix((1, 0), (usize::MAX, 1));
     }
 }
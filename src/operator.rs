@@ -0,0 +1,355 @@
+use std::borrow::Cow;
+
+use crate::{
+    CooMatrix, CscMatrix, CsrMatrix, Layout, Matrix, MatrixError, MatrixView, MatrixViewMut,
+    SMatrix,
+};
+
+use num_traits::Num;
+
+/// A linear map `y = Ax` from vectors of length `shape().1` to vectors of length `shape().0`,
+/// which is all that matrix-free algorithms such as `cg`, `gmres`, `power_iteration` and
+/// `lanczos` need to know about `A`.
+///
+/// It is implemented for `Matrix`, `SMatrix`, the views, the sparse matrix types and `FnOperator`
+/// (which wraps a closure), and can be implemented for operators that are never stored as a matrix.
+/// The solvers check the shape of the operator against the right hand side, so `apply` is only
+/// ever passed vectors of the lengths that `shape` implies. The implementations in this crate
+/// `debug_assert!` those lengths.
+/// ```
+/// use mtrs::{cg, LinearOperator, SolverOptions};
+///
+/// // The tridiagonal matrix with 2 on the diagonal and -1 next to it, never materialised
+/// struct SecondDifference(usize);
+///
+/// impl LinearOperator<f64> for SecondDifference {
+///     fn shape(&self) -> (usize, usize) {
+///         (self.0, self.0)
+///     }
+///
+///     fn apply(&self, x: &[f64], y: &mut [f64]) {
+///         for i in 0..self.0 {
+///             let left = if i > 0 { x[i - 1] } else { 0.0 };
+///             let right = if i + 1 < self.0 { x[i + 1] } else { 0.0 };
+///             y[i] = 2.0 * x[i] - left - right;
+///         }
+///     }
+/// }
+///
+/// let result = cg(&SecondDifference(50), &[1.0; 50], &SolverOptions::new()).unwrap();
+/// assert!(result.converged());
+/// ```
+pub trait LinearOperator<T: Num + Clone + Copy> {
+    /// The dimensions (`(height, width)`) of the operator as a matrix
+    fn shape(&self) -> (usize, usize);
+
+    /// Sets `y = Ax`, where `x` has `shape().1` entries and `y` has `shape().0`
+    fn apply(&self, x: &[T], y: &mut [T]);
+
+    /// Sets `y = Aᵀx`, where `x` has `shape().0` entries and `y` has `shape().1`
+    /// # Failure
+    /// Fails with `MatrixError::Unsupported` if the operator cannot apply its transpose (the
+    /// default)
+    fn apply_transpose(&self, _x: &[T], _y: &mut [T]) -> Result<(), MatrixError> {
+        Err(MatrixError::Unsupported)
+    }
+
+    /// The entries of the operator as a CSR matrix, which the Jacobi and ILU(0) preconditioners
+    /// are built from, or `None` if they are unknown (the default)
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        None
+    }
+}
+
+/// A `LinearOperator` of the given shape that applies a closure, setting `y = Ax` for the `x`
+/// and `y` it is passed
+/// ```
+/// use mtrs::{FnOperator, LinearOperator};
+///
+/// // Reverses and doubles a vector of length 3
+/// let operator = FnOperator::new((3, 3), |x: &[i32], y: &mut [i32]| {
+///     for (y, x) in y.iter_mut().zip(x.iter().rev()) {
+///         *y = 2 * x;
+///     }
+/// });
+///
+/// let mut y = [0; 3];
+/// operator.apply(&[1, 2, 3], &mut y);
+/// assert_eq!(y, [6, 4, 2]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct FnOperator<F> {
+    /// The dimensions (`(height, width)`) of the operator
+    shape: (usize, usize),
+
+    /// The closure that applies the operator
+    apply: F,
+}
+
+impl<F> FnOperator<F> {
+    /// Creates a new operator of the given shape (`(height, width)`) from a closure
+    pub fn new(shape: (usize, usize), apply: F) -> Self {
+        FnOperator { shape, apply }
+    }
+}
+
+impl<T: Num + Clone + Copy, F: Fn(&[T], &mut [T])> LinearOperator<T> for FnOperator<F> {
+    fn shape(&self) -> (usize, usize) {
+        self.shape
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        debug_check_lengths(self.shape, x, y);
+        (self.apply)(x, y)
+    }
+}
+
+impl<'a, T: Num + Clone + Copy> LinearOperator<T> for MatrixView<'a, T> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        debug_check_lengths(self.size(), x, y);
+        let width = self.size().1;
+        for (row, y) in y.iter_mut().enumerate() {
+            *y = (0..width).fold(T::zero(), |acc, col| acc + self.at(row, col) * x[col]);
+        }
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        self.transpose_view().apply(x, y);
+        Ok(())
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(CsrMatrix::from_dense(&self.to_matrix())))
+    }
+}
+
+impl<'a, T: Num + Clone + Copy> LinearOperator<T> for MatrixViewMut<'a, T> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        self.as_view().apply(x, y)
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        self.as_view().apply_transpose(x, y)
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(CsrMatrix::from_dense(&self.to_matrix())))
+    }
+}
+
+impl<T: Num + Clone + Copy, L: Layout> LinearOperator<T> for Matrix<T, L> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        self.as_view().apply(x, y)
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        self.as_view().apply_transpose(x, y)
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(CsrMatrix::from_dense(&self.to_row_major())))
+    }
+}
+
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> LinearOperator<T> for SMatrix<T, R, C> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        self.as_view().apply(x, y)
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        self.as_view().apply_transpose(x, y)
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(CsrMatrix::from_dense(&self.to_matrix())))
+    }
+}
+
+impl<T: Num + Clone + Copy> LinearOperator<T> for CsrMatrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        debug_check_lengths(self.size(), x, y);
+        let (indptr, indices, values) = (self.indptr(), self.indices(), self.values());
+        for (row, y) in y.iter_mut().enumerate() {
+            *y = (indptr[row]..indptr[row + 1]).fold(T::zero(), |acc, position| {
+                acc + values[position] * x[indices[position]]
+            });
+        }
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        let (height, width) = self.size();
+        debug_check_lengths((width, height), x, y);
+        scatter(
+            y,
+            self.iter().map(|((row, col), value)| (col, row, value)),
+            x,
+        );
+        Ok(())
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Borrowed(self))
+    }
+}
+
+impl<T: Num + Clone + Copy> LinearOperator<T> for CscMatrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        debug_check_lengths(self.size(), x, y);
+        scatter(
+            y,
+            self.iter().map(|((row, col), value)| (row, col, value)),
+            x,
+        );
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        let (height, width) = self.size();
+        debug_check_lengths((width, height), x, y);
+        let (indptr, indices, values) = (self.indptr(), self.indices(), self.values());
+        for (col, y) in y.iter_mut().enumerate() {
+            *y = (indptr[col]..indptr[col + 1]).fold(T::zero(), |acc, position| {
+                acc + values[position] * x[indices[position]]
+            });
+        }
+        Ok(())
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(self.to_csr()))
+    }
+}
+
+impl<T: Num + Clone + Copy> LinearOperator<T> for CooMatrix<T> {
+    fn shape(&self) -> (usize, usize) {
+        self.size()
+    }
+
+    fn apply(&self, x: &[T], y: &mut [T]) {
+        debug_check_lengths(self.size(), x, y);
+        scatter(
+            y,
+            self.iter().map(|((row, col), value)| (row, col, value)),
+            x,
+        );
+    }
+
+    fn apply_transpose(&self, x: &[T], y: &mut [T]) -> Result<(), MatrixError> {
+        let (height, width) = self.size();
+        debug_check_lengths((width, height), x, y);
+        scatter(
+            y,
+            self.iter().map(|((row, col), value)| (col, row, value)),
+            x,
+        );
+        Ok(())
+    }
+
+    fn as_csr(&self) -> Option<Cow<'_, CsrMatrix<T>>> {
+        Some(Cow::Owned(self.to_csr()))
+    }
+}
+
+// Checks, in debug builds, that `x` and `y` have the lengths that an operator of the given shape
+// maps between
+fn debug_check_lengths<T>((height, width): (usize, usize), x: &[T], y: &[T]) {
+    debug_assert_eq!(
+        x.len(),
+        width,
+        "the operator expects {} entries in x",
+        width
+    );
+    debug_assert_eq!(
+        y.len(),
+        height,
+        "the operator expects {} entries in y",
+        height
+    );
+}
+
+// Sets `y` to the sum of `value * x[from]` into `y[to]` over the `(to, from, value)` entries
+fn scatter<T, I>(y: &mut [T], entries: I, x: &[T])
+where
+    T: Num + Clone + Copy,
+    I: Iterator<Item = (usize, usize, T)>,
+{
+    y.iter_mut().for_each(|y| *y = T::zero());
+    for (to, from, value) in entries {
+        y[to] = y[to] + value * x[from];
+    }
+}
+
+#[cfg(test)]
+mod operator_tests {
+    use super::{FnOperator, LinearOperator};
+    use crate::{CooMatrix, CscMatrix, CsrMatrix, Matrix, MatrixError};
+
+    // Applies an operator and its transpose to fixed vectors
+    fn products<A: LinearOperator<i64>>(a: &A) -> (Vec<i64>, Result<Vec<i64>, MatrixError>) {
+        let (height, width) = a.shape();
+        let (x, x_t) = ((1..=width as i64).collect::<Vec<_>>(), vec![1, -1, 2]);
+        let (mut y, mut y_t) = (vec![0; height], vec![0; width]);
+
+        a.apply(&x, &mut y);
+        let transposed = a.apply_transpose(&x_t[..height], &mut y_t).map(|_| y_t);
+        (y, transposed)
+    }
+
+    #[test]
+    fn test_implementations() {
+        let dense = Matrix::from_vec((3, 4), vec![1, 0, 2, 0, 0, -3, 0, 4, 5, 0, 0, 6]);
+        let expected = (vec![7, 10, 29], Ok(vec![11, 3, 2, 8]));
+
+        assert_eq!(products(&dense), expected);
+        assert_eq!(products(&dense.to_col_major()), expected);
+        assert_eq!(products(&dense.as_view()), expected);
+        assert_eq!(products(&CsrMatrix::from_dense(&dense)), expected);
+        assert_eq!(products(&CscMatrix::from_dense(&dense)), expected);
+        assert_eq!(products(&CooMatrix::from_dense(&dense)), expected);
+
+        let block = dense.view(1.., 1..3);
+        assert_eq!(products(&block), (vec![-3, 0], Ok(vec![-3, 0])));
+        assert_eq!(
+            block.as_csr().unwrap().into_owned(),
+            CsrMatrix::from_dense(&block.to_matrix())
+        );
+
+        let closure = FnOperator::new((3, 4), |x: &[i64], y: &mut [i64]| dense.apply(x, y));
+        assert_eq!(
+            products(&closure),
+            (expected.0, Err(MatrixError::Unsupported))
+        );
+        assert!(closure.as_csr().is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the operator expects 3 entries in y")]
+    fn test_short_output() {
+        let sparse = CsrMatrix::from_dense(&Matrix::<i64>::identity(3));
+        sparse.apply(&[1, 2, 3], &mut [0; 2]);
+    }
+}
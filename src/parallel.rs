@@ -0,0 +1,216 @@
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The bounds that the entries of a `Matrix` need so that operations can be split between threads.
+///
+/// This is `Send + Sync`, which every primitive number type satisfies. It is required whether or
+/// not the `rayon` feature is enabled, so enabling the feature never removes operations from a
+/// matrix.
+pub trait Element: Send + Sync {}
+
+impl<T: Send + Sync> Element for T {}
+
+// Below this many entries (or multiply-adds) splitting the work between threads costs more than it
+// saves
+#[cfg(feature = "rayon")]
+pub(crate) const MIN_PARALLEL_WORK: usize = 1 << 14;
+
+// Builds a `Vec` from the value of `f` at every index up to `len`
+pub(crate) fn tabulate<T, F>(len: usize, f: F) -> Vec<T>
+where
+    T: Element,
+    F: Fn(usize) -> T + Element,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if len >= MIN_PARALLEL_WORK {
+            return (0..len).into_par_iter().map(f).collect();
+        }
+    }
+
+    (0..len).map(f).collect()
+}
+
+// Applies `f` to every entry of `data` in place
+pub(crate) fn for_each_mut<T, F>(data: &mut [T], f: F)
+where
+    T: Element,
+    F: Fn(&mut T) + Element,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if data.len() >= MIN_PARALLEL_WORK {
+            return data.par_iter_mut().for_each(f);
+        }
+    }
+
+    data.iter_mut().for_each(f)
+}
+
+// Combines every entry of `other` into the matching entry of `data` in place
+pub(crate) fn zip_mut<T, F>(data: &mut [T], other: &[T], f: F)
+where
+    T: Element + Copy,
+    F: Fn(&mut T, T) + Element,
+{
+    #[cfg(feature = "rayon")]
+    {
+        if data.len() >= MIN_PARALLEL_WORK {
+            return data
+                .par_iter_mut()
+                .zip(other.par_iter())
+                .for_each(|(left, &right)| f(left, right));
+        }
+    }
+
+    data.iter_mut()
+        .zip(other.iter())
+        .for_each(|(left, &right)| f(left, right))
+}
+
+// Applies `f` to every `len` long chunk of `data` (the last may be shorter) along with its index
+pub(crate) fn for_each_chunk<T, F>(data: &mut [T], len: usize, f: F)
+where
+    T: Element,
+    F: Fn(usize, &mut [T]) + Element,
+{
+    if len == 0 {
+        return;
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        if data.len() >= MIN_PARALLEL_WORK {
+            return data
+                .par_chunks_mut(len)
+                .enumerate()
+                .for_each(|(index, chunk)| f(index, chunk));
+        }
+    }
+
+    data.chunks_mut(len)
+        .enumerate()
+        .for_each(|(index, chunk)| f(index, chunk))
+}
+
+// Applies `f` to every `width` long row of `data`, stopping early if it returns `None`. Each row is
+// processed exactly as it would be serially, so only the order between rows differs.
+pub(crate) fn try_for_each_row<T, F>(data: &mut [T], width: usize, f: F) -> Option<()>
+where
+    T: Element,
+    F: Fn(&mut [T]) -> Option<()> + Element,
+{
+    if width == 0 {
+        return Some(());
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        if data.len() >= MIN_PARALLEL_WORK {
+            return data.par_chunks_mut(width).try_for_each(f);
+        }
+    }
+
+    data.chunks_mut(width).try_for_each(f)
+}
+
+// Applies `f` to every `width` long row of `data`, see `try_for_each_row`
+pub(crate) fn for_each_row<T, F>(data: &mut [T], width: usize, f: F)
+where
+    T: Element,
+    F: Fn(&mut [T]) + Element,
+{
+    try_for_each_row(data, width, |row| {
+        f(row);
+        Some(())
+    });
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use crate::test_util::sample;
+    use crate::Matrix;
+
+    #[cfg(feature = "rayon")]
+    use rayon::ThreadPoolBuilder;
+
+    // Its leading minors are `2, 3, 4, ...`, so fraction-free elimination cannot overflow
+    fn tridiagonal(n: usize) -> Matrix<i64> {
+        Matrix::from_vec(
+            n,
+            (0..n * n)
+                .map(|i| match (i / n) as i64 - (i % n) as i64 {
+                    0 => 2,
+                    1 | -1 => -1,
+                    _ => 0,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_matches_reference() {
+        // Large enough to take the parallel path of every operation when `rayon` is enabled, and
+        // checked entry by entry against plain loops, so the serial build is tested the same way
+        let (n, a, b) = (150, sample((150, 150), 1), sample((150, 150), 2));
+
+        let product = &a * &b;
+        let (sum, scaled, transposed) = (a.try_add(&b).unwrap(), a.scalar_mul(-7), a.transposed());
+        for row in 0..n {
+            for col in 0..n {
+                let dot = (0..n).map(|k| a[(row, k)] * b[(k, col)]).sum::<i64>();
+                assert_eq!(product[(row, col)], dot);
+                assert_eq!(sum[(row, col)], a[(row, col)] + b[(row, col)]);
+                assert_eq!(scaled[(row, col)], -7 * a[(row, col)]);
+                assert_eq!(transposed[(row, col)], a[(col, row)]);
+            }
+        }
+
+        assert_eq!(tridiagonal(150).determinant(), Some(151));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_matches_single_thread() {
+        let a = sample((150, 150), 1);
+        let b = sample((150, 150), 2).scalar_add(3);
+        let tridiagonal = tridiagonal(60);
+
+        let single = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let parallel = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let run = || {
+            (
+                &a * &b,
+                a.try_add(&b).unwrap(),
+                a.scalar_mul(-7),
+                a.transposed(),
+                tridiagonal.determinant(),
+            )
+        };
+
+        assert_eq!(single.install(run), parallel.install(run));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_float_decompositions() {
+        let matrix: Matrix<f64> = Matrix::from_vec(
+            130,
+            (0..130 * 130)
+                .map(|i| {
+                    if i % 131 == 0 {
+                        200.0
+                    } else {
+                        ((i * 37) % 11) as f64
+                    }
+                })
+                .collect(),
+        );
+
+        let single = ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let parallel = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+        let run = || (matrix.determinant(), matrix.lu().unwrap().u());
+
+        assert_eq!(single.install(run), parallel.install(run));
+    }
+}
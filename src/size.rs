@@ -1,3 +1,5 @@
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
 /// Is implemented for both `usize` and `(usize, usize)`, which allows for a more flexible input
 /// when creating matrices
 pub trait Size {
@@ -15,3 +17,53 @@ impl Size for (usize, usize) {
         (self.0, self.1)
     }
 }
+
+/// Is implemented for `usize` and the range types (including `..`), which allows for a block of
+/// rows or columns to be selected from a matrix. A single `usize` selects just that row or column.
+pub trait SliceRange {
+    /// The selected indices, for a dimension of length `len`, or `None` if the end of the range
+    /// overflows. The range is not checked against `len`.
+    fn range(&self, len: usize) -> Option<Range<usize>>;
+}
+
+impl SliceRange for usize {
+    fn range(&self, _: usize) -> Option<Range<usize>> {
+        Some(*self..self.checked_add(1)?)
+    }
+}
+
+impl SliceRange for Range<usize> {
+    fn range(&self, _: usize) -> Option<Range<usize>> {
+        Some(self.clone())
+    }
+}
+
+impl SliceRange for RangeInclusive<usize> {
+    fn range(&self, _: usize) -> Option<Range<usize>> {
+        Some(*self.start()..self.end().checked_add(1)?)
+    }
+}
+
+impl SliceRange for RangeFrom<usize> {
+    fn range(&self, len: usize) -> Option<Range<usize>> {
+        Some(self.start..len)
+    }
+}
+
+impl SliceRange for RangeTo<usize> {
+    fn range(&self, _: usize) -> Option<Range<usize>> {
+        Some(0..self.end)
+    }
+}
+
+impl SliceRange for RangeToInclusive<usize> {
+    fn range(&self, _: usize) -> Option<Range<usize>> {
+        Some(0..self.end.checked_add(1)?)
+    }
+}
+
+impl SliceRange for RangeFull {
+    fn range(&self, len: usize) -> Option<Range<usize>> {
+        Some(0..len)
+    }
+}
@@ -0,0 +1,371 @@
+use std::convert::TryFrom;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use crate::size::Size;
+use crate::{Matrix, MatrixError, MatrixView, MatrixViewMut};
+
+use num_traits::Num;
+
+/// A matrix whose `R` rows and `C` columns are fixed at compile time, stored inline as an array
+/// of rows instead of on the heap. The sizes of sums and products are checked by the compiler,
+/// so the operators cannot fail.
+/// ```
+/// use mtrs::{Matrix, SMatrix};
+///
+/// let a = SMatrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+/// let b = SMatrix::from_rows([[1, 0], [0, 1], [1, 1]]);
+/// let product: SMatrix<i32, 2, 2> = a * b;
+///
+/// assert_eq!(product, SMatrix::from_rows([[4, 5], [10, 11]]));
+/// assert_eq!(Matrix::from(product), Matrix::from(a) * Matrix::from(b));
+/// ```
+///
+/// A product of mismatched sizes does not compile:
+/// ```compile_fail
+/// use mtrs::SMatrix;
+///
+/// let a = SMatrix::<i32, 2, 3>::zeros();
+/// let _ = a * a;
+/// ```
+///
+/// The decompositions and solvers of `Matrix` are reached through `as_view` (see `MatrixView`),
+/// and the iterative solvers take the matrix itself as a `LinearOperator`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SMatrix<T: Num, const R: usize, const C: usize> {
+    /// The rows of the matrix
+    data: [[T; C]; R],
+}
+
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> SMatrix<T, R, C> {
+    /// Creates a new matrix from its rows
+    pub fn from_rows(rows: [[T; C]; R]) -> Self {
+        SMatrix { data: rows }
+    }
+
+    /// Creates a new matrix whose entry at `(row, col)` is `entry(row, col)`, visiting the entries
+    /// row by row
+    /// ```
+    /// use mtrs::SMatrix;
+    ///
+    /// let matrix = SMatrix::<usize, 2, 3>::from_fn(|row, col| row * 3 + col);
+    ///
+    /// assert_eq!(matrix.as_slice(), &[0, 1, 2, 3, 4, 5]);
+    /// ```
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut entry: F) -> Self {
+        let mut data = [[T::zero(); C]; R];
+        for (row, values) in data.iter_mut().enumerate() {
+            for (col, value) in values.iter_mut().enumerate() {
+                *value = entry(row, col);
+            }
+        }
+
+        SMatrix { data }
+    }
+
+    /// Create a matrix filled with `0`s
+    pub fn zeros() -> Self {
+        SMatrix {
+            data: [[T::zero(); C]; R],
+        }
+    }
+
+    /// Create a matrix filled with `1`s
+    pub fn ones() -> Self {
+        SMatrix {
+            data: [[T::one(); C]; R],
+        }
+    }
+
+    /// Returns a tuple representing the dimensions (`(height, width)`), which is `(R, C)`
+    pub fn size(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    /// The rows of the matrix
+    pub fn rows(&self) -> &[[T; C]; R] {
+        &self.data
+    }
+
+    /// Take the rows of the matrix
+    pub fn into_rows(self) -> [[T; C]; R] {
+        self.data
+    }
+
+    /// The entries of the matrix, row by row
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_flattened()
+    }
+
+    /// The entries of the matrix, row by row, mutably
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_flattened_mut()
+    }
+
+    /// Returns an entry of the matrix safely, or `None` if it is out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<&T> {
+        let (row, col) = loc.dim();
+        self.data.get(row).and_then(|values| values.get(col))
+    }
+
+    /// Returns a mutable reference to an entry of the matrix, or `None` if it is out of bounds
+    pub fn get_mut<S: Size>(&mut self, loc: S) -> Option<&mut T> {
+        let (row, col) = loc.dim();
+        self.data
+            .get_mut(row)
+            .and_then(|values| values.get_mut(col))
+    }
+
+    /// Borrow the matrix as a `MatrixView`, which has the arithmetic and decompositions of
+    /// `Matrix`
+    /// ```
+    /// use mtrs::SMatrix;
+    ///
+    /// let a = SMatrix::from_rows([[4.0_f64, 1.0], [1.0, 3.0]]);
+    /// let x = a.as_view().solve_vec(&[5.0, 4.0]).unwrap();
+    ///
+    /// assert!((x[0] - 1.0).abs() < 1e-12 && (x[1] - 1.0).abs() < 1e-12);
+    /// assert_eq!(a.as_view().determinant(), Some(11.0));
+    /// ```
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView::from_row_major(self.data.as_flattened(), (R, C))
+    }
+
+    /// Borrow the matrix as a `MatrixViewMut`
+    pub fn as_view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        MatrixViewMut::from_row_major(self.data.as_flattened_mut(), (R, C))
+    }
+
+    /// Copy the entries into a new `Matrix`, see also `Matrix::from`
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix::from_slice((R, C), self.as_slice())
+    }
+
+    /// Returns the transpose of the matrix, whose size is swapped at compile time
+    /// ```
+    /// use mtrs::SMatrix;
+    ///
+    /// let matrix = SMatrix::from_rows([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// assert_eq!(matrix.transposed(), SMatrix::from_rows([[1, 4], [2, 5], [3, 6]]));
+    /// ```
+    pub fn transposed(&self) -> SMatrix<T, C, R> {
+        SMatrix::from_fn(|row, col| self.data[col][row])
+    }
+
+    /// Multiply each entry by `value`
+    pub fn scalar_mul(&self, value: T) -> Self {
+        SMatrix::from_fn(|row, col| self.data[row][col] * value)
+    }
+}
+
+impl<T: Num + Clone + Copy, const N: usize> SMatrix<T, N, N> {
+    /// Create an identity matrix
+    pub fn identity() -> Self {
+        SMatrix::from_fn(|row, col| if row == col { T::one() } else { T::zero() })
+    }
+
+    /// The sum of the diagonal entries
+    pub fn trace(&self) -> T {
+        (0..N).fold(T::zero(), |acc, i| acc + self.data[i][i])
+    }
+}
+
+/// Allows for the indexing of `SMatrix`
+impl<T: Num + Clone + Copy, S: Size, const R: usize, const C: usize> Index<S> for SMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, pos: S) -> &Self::Output {
+        let (h, w) = pos.dim();
+        self.get((h, w)).unwrap_or_else(|| {
+            panic!(
+                "{}",
+                MatrixError::OutOfBounds {
+                    index: (h, w),
+                    size: (R, C),
+                }
+            )
+        })
+    }
+}
+
+/// Allows for mutable indexing of `SMatrix`
+impl<T: Num + Clone + Copy, S: Size, const R: usize, const C: usize> IndexMut<S>
+    for SMatrix<T, R, C>
+{
+    fn index_mut(&mut self, pos: S) -> &mut Self::Output {
+        let (h, w) = pos.dim();
+        self.get_mut((h, w)).unwrap_or_else(|| {
+            panic!(
+                "{}",
+                MatrixError::OutOfBounds {
+                    index: (h, w),
+                    size: (R, C),
+                }
+            )
+        })
+    }
+}
+
+/// Implements addition between matrices of the same size
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> Add for SMatrix<T, R, C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        SMatrix::from_fn(|row, col| self.data[row][col] + other.data[row][col])
+    }
+}
+
+/// Implements subtraction between matrices of the same size
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> Sub for SMatrix<T, R, C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        SMatrix::from_fn(|row, col| self.data[row][col] - other.data[row][col])
+    }
+}
+
+/// Implements multiplication by a matrix with as many rows as `self` has columns
+impl<T: Num + Clone + Copy, const R: usize, const C: usize, const K: usize> Mul<SMatrix<T, C, K>>
+    for SMatrix<T, R, C>
+{
+    type Output = SMatrix<T, R, K>;
+
+    fn mul(self, other: SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+        SMatrix::from_fn(|row, col| {
+            (0..C).fold(T::zero(), |acc, i| {
+                acc + self.data[row][i] * other.data[i][col]
+            })
+        })
+    }
+}
+
+/// Implements the `From<[[T; C]; R]>` trait for `SMatrix<T, R, C>`, where each inner array is a
+/// row
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> From<[[T; C]; R]> for SMatrix<T, R, C> {
+    fn from(rows: [[T; C]; R]) -> Self {
+        SMatrix::from_rows(rows)
+    }
+}
+
+/// Implements the `From<SMatrix<T, R, C>>` trait for `Matrix<T>`
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(matrix: SMatrix<T, R, C>) -> Self {
+        matrix.to_matrix()
+    }
+}
+
+/// Implements the `TryFrom<&Matrix<T>>` trait for `SMatrix<T, R, C>`, which copies the entries
+/// ```
+/// use std::convert::TryFrom;
+/// use mtrs::{Matrix, MatrixError, SMatrix};
+///
+/// let matrix = Matrix::from_vec((2, 2), vec![1, 2, 3, 4]);
+///
+/// assert_eq!(SMatrix::try_from(&matrix), Ok(SMatrix::from_rows([[1, 2], [3, 4]])));
+/// assert_eq!(
+///     SMatrix::<i32, 1, 4>::try_from(&matrix),
+///     Err(MatrixError::DimensionMismatch { left: (1, 4), right: (2, 2) })
+/// );
+/// ```
+/// # Failure
+/// Fails with `MatrixError::DimensionMismatch` if the matrix is not `R * C`
+impl<'a, T: Num + Clone + Copy, const R: usize, const C: usize> TryFrom<&'a Matrix<T>>
+    for SMatrix<T, R, C>
+{
+    type Error = MatrixError;
+
+    fn try_from(matrix: &'a Matrix<T>) -> Result<Self, MatrixError> {
+        if matrix.size() != (R, C) {
+            return Err(MatrixError::DimensionMismatch {
+                left: (R, C),
+                right: matrix.size(),
+            });
+        }
+
+        Ok(SMatrix::from_fn(|row, col| matrix[(row, col)]))
+    }
+}
+
+/// Implements the `TryFrom<Matrix<T>>` trait for `SMatrix<T, R, C>`, see the `TryFrom<&Matrix<T>>`
+/// implementation
+impl<T: Num + Clone + Copy, const R: usize, const C: usize> TryFrom<Matrix<T>>
+    for SMatrix<T, R, C>
+{
+    type Error = MatrixError;
+
+    fn try_from(matrix: Matrix<T>) -> Result<Self, MatrixError> {
+        SMatrix::try_from(&matrix)
+    }
+}
+
+/// Pretty print of the `SMatrix`, the same as that of `Matrix`
+impl<T: Num + Clone + Copy + Display, const R: usize, const C: usize> Display for SMatrix<T, R, C> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.to_matrix().fmt(fmt)
+    }
+}
+
+#[cfg(test)]
+mod smatrix_tests {
+    use std::convert::TryFrom;
+
+    use super::SMatrix;
+    use crate::{cg, LinearOperator, Matrix, MatrixError, SolverOptions};
+
+    #[test]
+    fn test_arithmetic() {
+        let a = SMatrix::from_rows([[1, -2, 0], [3, 4, 5]]);
+        let b = SMatrix::<i32, 3, 4>::from_fn(|row, col| (row * 4 + col) as i32 - 5);
+        let (dense_a, dense_b) = (Matrix::from(a), Matrix::from(b));
+
+        assert_eq!(Matrix::from(a * b), &dense_a * &dense_b);
+        assert_eq!(Matrix::from(a + a), &dense_a + &dense_a);
+        assert_eq!(Matrix::from(a - a.scalar_mul(3)), dense_a.scalar_mul(-2));
+        assert_eq!(Matrix::from(a.transposed()), dense_a.transposed());
+        assert_eq!(a * SMatrix::identity(), a);
+        assert_eq!(SMatrix::<i32, 3, 3>::ones().trace(), 3);
+        assert_eq!(format!("{}", a), format!("{}", dense_a));
+    }
+
+    #[test]
+    fn test_access() {
+        let mut matrix = SMatrix::<i32, 2, 3>::zeros();
+        matrix[(1, 2)] = 7;
+        *matrix.get_mut((0, 1)).unwrap() = 4;
+        matrix.as_view_mut().row_mut(1).fill(1);
+
+        assert_eq!(matrix.into_rows(), [[0, 4, 0], [1, 1, 1]]);
+        assert_eq!(matrix.get((2, 0)), None);
+        assert_eq!(matrix.as_view().col(1).to_matrix().as_slice(), &[4, 1]);
+        assert_eq!(
+            SMatrix::<i32, 3, 2>::try_from(Matrix::from(matrix)),
+            Err(MatrixError::DimensionMismatch {
+                left: (3, 2),
+                right: (2, 3)
+            })
+        );
+        assert_eq!(SMatrix::try_from(matrix.to_matrix()), Ok(matrix));
+    }
+
+    #[test]
+    #[should_panic(expected = "index (0, 2) is out of bounds for a 2x2 matrix")]
+    fn test_out_of_bounds() {
+        let matrix = SMatrix::<i32, 2, 2>::identity();
+        let _ = matrix[(0, 2)];
+    }
+
+    #[test]
+    fn test_operator() {
+        let a = SMatrix::from_rows([[4.0_f64, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let mut y = [0.0; 3];
+        a.apply(&[1.0, 1.0, 1.0], &mut y);
+        assert_eq!(y, [5.0, 5.0, 3.0]);
+
+        let result = cg(&a, &y, &SolverOptions::new()).unwrap();
+        assert!(result.converged());
+        for x in result.solution() {
+            assert!((x - 1.0).abs() < 1e-6);
+        }
+    }
+}
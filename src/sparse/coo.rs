@@ -0,0 +1,285 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::Compressed;
+use crate::size::Size;
+use crate::sparse::{CscMatrix, CsrMatrix};
+use crate::{Matrix, MatrixError};
+
+use num_traits::Num;
+
+/// A sparse matrix in coordinate (COO) form, a list of `(row, col, value)` triplets in any order.
+///
+/// This is the form to assemble a matrix in, one entry at a time, before converting it to a
+/// `CsrMatrix` or `CscMatrix` to compute with. Entries pushed more than once at the same location
+/// are summed by the conversions, as when assembling finite element stiffness matrices.
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::CooMatrix;
+///
+/// let mut coo = CooMatrix::new((2, 3));
+/// coo.push((0, 2), 4).unwrap();
+/// coo.push((1, 0), 1).unwrap();
+/// coo.push((0, 2), 3).unwrap();
+///
+/// assert_eq!(coo.nnz(), 3);
+/// assert_eq!(coo.to_dense(), matrix![(2, 3); 0, 0, 7; 1, 0, 0]);
+/// assert_eq!(coo.to_csr().values(), &[7, 1]);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct CooMatrix<T: Num> {
+    /// The height of the matrix
+    height: usize,
+
+    /// The width of the matrix
+    width: usize,
+
+    /// The row of each entry
+    rows: Vec<usize>,
+
+    /// The column of each entry
+    cols: Vec<usize>,
+
+    /// The value of each entry
+    values: Vec<T>,
+}
+
+impl<T: Num + Clone + Copy> CooMatrix<T> {
+    /// Creates a new matrix of the given size, with no entries
+    pub fn new<S: Size>(size: S) -> Self {
+        let (height, width) = size.dim();
+        CooMatrix {
+            height,
+            width,
+            rows: Vec::new(),
+            cols: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    /// Creates a new matrix from the rows, columns and values of its entries
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if `cols` or `values` is not as long as `rows`,
+    /// and with `MatrixError::OutOfBounds` if an entry is out of bounds
+    pub fn try_from_triplets<S: Size>(
+        size: S,
+        rows: Vec<usize>,
+        cols: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, MatrixError> {
+        let (height, width) = size.dim();
+        for len in [cols.len(), values.len()].iter() {
+            if *len != rows.len() {
+                return Err(MatrixError::InvalidLength {
+                    expected: rows.len(),
+                    found: *len,
+                });
+            }
+        }
+
+        if let Some((&row, &col)) = rows
+            .iter()
+            .zip(cols.iter())
+            .find(|(&row, &col)| row >= height || col >= width)
+        {
+            return Err(MatrixError::OutOfBounds {
+                index: (row, col),
+                size: (height, width),
+            });
+        }
+
+        Ok(CooMatrix {
+            height,
+            width,
+            rows,
+            cols,
+            values,
+        })
+    }
+
+    /// Creates a new matrix holding the non-zero entries of a dense matrix
+    pub fn from_dense(matrix: &Matrix<T>) -> Self {
+        let mut coo = Self::new(matrix.size());
+        for (loc, &value) in matrix.indexed_iter() {
+            if !value.is_zero() {
+                coo.push(loc, value).unwrap();
+            }
+        }
+
+        coo
+    }
+
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// The number of entries, counting repeated locations separately
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Add an entry to the matrix, on top of any already at the same location
+    /// # Failure
+    /// Fails with `MatrixError::OutOfBounds` if the location is out of bounds
+    pub fn push<S: Size>(&mut self, loc: S, value: T) -> Result<(), MatrixError> {
+        let (row, col) = loc.dim();
+        if row >= self.height || col >= self.width {
+            return Err(MatrixError::OutOfBounds {
+                index: (row, col),
+                size: self.size(),
+            });
+        }
+
+        self.rows.push(row);
+        self.cols.push(col);
+        self.values.push(value);
+        Ok(())
+    }
+
+    /// The row of each entry
+    pub fn row_indices(&self) -> &[usize] {
+        &self.rows
+    }
+
+    /// The column of each entry
+    pub fn col_indices(&self) -> &[usize] {
+        &self.cols
+    }
+
+    /// The value of each entry
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    /// Iterate over the entries as `((row, col), value)`, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), T)> + '_ {
+        self.rows
+            .iter()
+            .zip(self.cols.iter())
+            .zip(self.values.iter())
+            .map(|((&row, &col), &value)| ((row, col), value))
+    }
+
+    /// Copy the matrix into a dense `Matrix`, summing repeated locations
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut dense = Matrix::zeros(self.size());
+        for ((row, col), value) in self.iter() {
+            let entry = &mut dense.data[row * self.width + col];
+            *entry = *entry + value;
+        }
+
+        dense
+    }
+
+    /// Convert the matrix to compressed sparse row form, summing repeated locations
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        let entries = self.iter().map(|((row, col), value)| (row, col, value));
+        CsrMatrix::from_compressed(Compressed::from_entries(self.size(), entries.collect()))
+    }
+
+    /// Convert the matrix to compressed sparse column form, summing repeated locations
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        let entries = self.iter().map(|((row, col), value)| (col, row, value));
+        let size = (self.width, self.height);
+        CscMatrix::from_compressed(Compressed::from_entries(size, entries.collect()))
+    }
+
+    /// Returns the transpose of the matrix as a new matrix
+    pub fn transposed(&self) -> Self {
+        let mut transposed = self.clone();
+        transposed.transpose();
+        transposed
+    }
+
+    /// Transposes the matrix, which only swaps the rows and columns of the entries
+    pub fn transpose(&mut self) {
+        std::mem::swap(&mut self.rows, &mut self.cols);
+        std::mem::swap(&mut self.height, &mut self.width);
+    }
+
+    /// Add a sparse matrix of the same size, by appending its entries to those of `self`
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let mut sum = self.clone();
+        sum.rows.extend_from_slice(&other.rows);
+        sum.cols.extend_from_slice(&other.cols);
+        sum.values.extend_from_slice(&other.values);
+        Ok(sum)
+    }
+}
+
+/// Pretty print of the sparse matrix, which prints every entry (including the zeros) the same way
+/// as `Matrix`
+impl<T: Num + Clone + Copy + Display> Display for CooMatrix<T> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        self.to_dense().fmt(fmt)
+    }
+}
+
+#[cfg(test)]
+mod coo_tests {
+    use super::CooMatrix;
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_assembly() {
+        // Two overlapping 2 * 2 element matrices on a chain of three nodes
+        let mut coo = CooMatrix::new(3);
+        for &start in &[0, 1] {
+            for &(row, col, value) in &[(0, 0, 1), (0, 1, -1), (1, 0, -1), (1, 1, 1)] {
+                coo.push((start + row, start + col), value).unwrap();
+            }
+        }
+
+        let expected = Matrix::from_vec(3, vec![1, -1, 0, -1, 2, -1, 0, -1, 1]);
+        assert_eq!(coo.nnz(), 8);
+        assert_eq!(coo.to_dense(), expected);
+        assert_eq!(coo.to_csr().to_dense(), expected);
+        assert_eq!(coo.to_csc().to_dense(), expected);
+        assert_eq!(coo.to_csr().nnz(), 7);
+        assert_eq!(format!("{}", coo), " 1 -1  0 \n-1  2 -1 \n 0 -1  1 \n");
+
+        let tall = CooMatrix::from_dense(&Matrix::from_vec((3, 2), vec![0, 1, 2, 0, 0, 3]));
+        assert_eq!(
+            tall.transposed().to_dense(),
+            Matrix::from_vec((2, 3), vec![0, 2, 0, 1, 0, 3])
+        );
+        assert_eq!(tall.try_add(&tall).unwrap().to_csr().values(), &[2, 4, 6]);
+    }
+
+    #[test]
+    fn test_invalid_entries() {
+        let mut coo = CooMatrix::new((2, 2));
+
+        assert_eq!(
+            coo.push((2, 0), 1),
+            Err(MatrixError::OutOfBounds {
+                index: (2, 0),
+                size: (2, 2)
+            })
+        );
+        assert_eq!(
+            CooMatrix::try_from_triplets(2, vec![0, 1], vec![1], vec![5, 6]),
+            Err(MatrixError::InvalidLength {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            CooMatrix::try_from_triplets(2, vec![0, 1], vec![1, 2], vec![5, 6]),
+            Err(MatrixError::OutOfBounds {
+                index: (1, 2),
+                size: (2, 2)
+            })
+        );
+        assert!(coo.try_add(&CooMatrix::new(3)).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Mul};
+
+use super::{impl_sparse_ops, Compressed};
+use crate::size::Size;
+use crate::sparse::{CooMatrix, CsrMatrix};
+use crate::{Matrix, MatrixError};
+
+use num_traits::Num;
+
+/// A sparse matrix in compressed sparse column (CSC) form, which stores only the non-zero entries,
+/// column by column. Column `j` holds the entries `indptr[j]..indptr[j + 1]` of `indices` (their
+/// rows, in increasing order) and `values`.
+///
+/// It supports the same operations as `CsrMatrix`, with fast column access instead of row
+/// access, and is the form expected by many direct solvers.
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::CscMatrix;
+///
+/// let dense = matrix![(3, 3); 1, 0, 2; 0, 0, 3; 4, 5, 0];
+/// let sparse = CscMatrix::from_dense(&dense);
+///
+/// assert_eq!(sparse.indptr(), &[0, 2, 3, 5]);
+/// assert_eq!(sparse.indices(), &[0, 2, 2, 0, 1]);
+/// assert_eq!(&sparse * &dense, &dense * &dense);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct CscMatrix<T: Num> {
+    /// The columns are the lines of the compressed storage
+    data: Compressed<T>,
+}
+
+impl<T: Num + Clone + Copy> CscMatrix<T> {
+    /// Creates a new CSC matrix from its column pointers, row indices and values
+    /// ```
+    /// use mtrs::{CscMatrix, MatrixError};
+    ///
+    /// let sparse = CscMatrix::try_from_parts((3, 2), vec![0, 1, 2], vec![2, 0], vec![7, 8]);
+    /// assert_eq!(sparse.unwrap().get((2, 0)), Some(7));
+    ///
+    /// let out_of_bounds = CscMatrix::try_from_parts((3, 1), vec![0, 1], vec![3], vec![7]);
+    /// assert_eq!(
+    ///     out_of_bounds,
+    ///     Err(MatrixError::OutOfBounds { index: (3, 0), size: (3, 1) })
+    /// );
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if `indptr` does not have `width + 1` entries or
+    /// `values` is not as long as `indices`, with `MatrixError::OutOfBounds` if a row is out of
+    /// bounds, and with `MatrixError::InvalidStructure` if `indptr` does not run from `0` up to the
+    /// number of entries, or the rows of a column are not strictly increasing
+    pub fn try_from_parts<S: Size>(
+        size: S,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, MatrixError> {
+        let (height, width) = size.dim();
+        match Compressed::try_new((width, height), indptr, indices, values) {
+            Ok(data) => Ok(CscMatrix { data }),
+            // The compressed storage reports locations as `(col, row)`
+            Err(MatrixError::OutOfBounds { index, size }) => Err(MatrixError::OutOfBounds {
+                index: (index.1, index.0),
+                size: (size.1, size.0),
+            }),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Creates a new CSC matrix holding the non-zero entries of a dense matrix
+    pub fn from_dense(matrix: &Matrix<T>) -> Self {
+        let (height, width) = matrix.size();
+        CscMatrix {
+            data: Compressed::from_fn((width, height), |col, row| matrix[(row, col)]),
+        }
+    }
+
+    // Wraps compressed storage whose lines are columns
+    pub(super) fn from_compressed(data: Compressed<T>) -> Self {
+        CscMatrix { data }
+    }
+
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    pub fn size(&self) -> (usize, usize) {
+        (self.data.inner, self.data.outer)
+    }
+
+    /// The number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.data.values.len()
+    }
+
+    /// The positions in `indices` and `values` where each column starts, followed by `nnz`
+    pub fn indptr(&self) -> &[usize] {
+        &self.data.indptr
+    }
+
+    /// The row of each stored entry
+    pub fn indices(&self) -> &[usize] {
+        &self.data.indices
+    }
+
+    /// The value of each stored entry
+    pub fn values(&self) -> &[T] {
+        &self.data.values
+    }
+
+    /// Returns an entry of the matrix (zero if it is not stored), or `None` if the location is
+    /// out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<T> {
+        let (row, col) = loc.dim();
+        if row < self.data.inner && col < self.data.outer {
+            Some(self.data.get(col, row))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the stored entries as `((row, col), value)`, column by column
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), T)> + '_ {
+        self.data
+            .entries()
+            .map(|(col, row, value)| ((row, col), value))
+    }
+
+    /// Copy the matrix into a dense `Matrix`
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut dense = Matrix::zeros(self.size());
+        for ((row, col), value) in self.iter() {
+            dense.data[row * self.data.outer + col] = value;
+        }
+
+        dense
+    }
+
+    /// Convert the matrix to coordinate form
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut coo = CooMatrix::new(self.size());
+        for (loc, value) in self.iter() {
+            coo.push(loc, value).unwrap();
+        }
+
+        coo
+    }
+
+    /// Convert the matrix to compressed sparse row form
+    pub fn to_csr(&self) -> CsrMatrix<T> {
+        CsrMatrix::from_compressed(self.data.transposed())
+    }
+
+    /// Returns the transpose of the matrix as a new matrix
+    pub fn transposed(&self) -> Self {
+        CscMatrix {
+            data: self.data.transposed(),
+        }
+    }
+
+    /// Transposes the matrix, see `CscMatrix::transposed`
+    pub fn transpose(&mut self) {
+        *self = self.transposed();
+    }
+
+    /// Add a sparse matrix of the same size, entry by entry
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        Ok(CscMatrix {
+            data: self.data.add(&other.data),
+        })
+    }
+
+    /// Multiply two sparse matrices, where the width of `self` must equal the height of `other`
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.data.outer != other.data.inner {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        // The columns of `AB` are the rows of `BᵀAᵀ`, whose factors are stored by row here
+        Ok(CscMatrix {
+            data: other.data.mul(&self.data),
+        })
+    }
+
+    /// Multiply by a dense matrix, where the width of `self` must equal the height of `other`
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul_dense(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.data.outer != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        // Each stored entry adds a multiple of a row of `other` to a row of the product
+        let width = other.width;
+        let mut product = Matrix::zeros((self.data.inner, width));
+        for (col, other_row) in other.rows().enumerate() {
+            for position in self.data.range(col) {
+                let (row, value) = (self.data.indices[position], self.data.values[position]);
+                let product_row = &mut product.data[row * width..(row + 1) * width];
+                for (entry, &right) in product_row.iter_mut().zip(other_row) {
+                    *entry = *entry + value * right;
+                }
+            }
+        }
+
+        Ok(product)
+    }
+}
+
+impl_sparse_ops!(CscMatrix);
+
+#[cfg(test)]
+mod csc_tests {
+    use super::CscMatrix;
+    use crate::sparse::test_sparse_api;
+    use crate::test_util::sparse_sample;
+    use crate::{Matrix, MatrixError};
+
+    test_sparse_api!(CscMatrix, to_csc, to_csr);
+
+    #[test]
+    fn test_indptr() {
+        let empty = CscMatrix::from_dense(&Matrix::<i32>::zeros((0, 3)));
+        let sparse = CscMatrix::from_dense(&Matrix::from_vec((2, 3), vec![0, 1, 2, 0, 0, 3]));
+
+        assert_eq!(empty.indptr(), &[0, 0, 0, 0]);
+        assert_eq!(sparse.indptr(), &[0, 0, 1, 3]);
+        assert_eq!(sparse.indices(), &[0, 0, 1]);
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Add, Mul};
+
+use super::{impl_sparse_ops, Compressed};
+use crate::size::Size;
+use crate::sparse::{CooMatrix, CscMatrix};
+use crate::{Matrix, MatrixError};
+
+use num_traits::Num;
+
+/// A sparse matrix in compressed sparse row (CSR) form, which stores only the non-zero entries,
+/// row by row. Row `i` holds the entries `indptr[i]..indptr[i + 1]` of `indices` (their columns,
+/// in increasing order) and `values`.
+///
+/// This is the form to compute with: it has fast row access, products with dense matrices and
+/// other CSR matrices, and elementwise sums. Build one from a `CooMatrix`, or from a dense
+/// `Matrix`. Zeros produced by arithmetic stay stored.
+/// ```
+/// #[macro_use] extern crate mtrs;
+/// use mtrs::CsrMatrix;
+///
+/// let dense = matrix![(3, 3); 1, 0, 2; 0, 0, 3; 4, 5, 0];
+/// let sparse = CsrMatrix::from_dense(&dense);
+///
+/// assert_eq!(sparse.nnz(), 5);
+/// assert_eq!(sparse.indptr(), &[0, 2, 3, 5]);
+/// assert_eq!(sparse.indices(), &[0, 2, 2, 0, 1]);
+/// assert_eq!(&sparse * &dense, &dense * &dense);
+/// assert_eq!((&sparse + &sparse).to_dense(), dense.scalar_mul(2));
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct CsrMatrix<T: Num> {
+    /// The rows are the lines of the compressed storage
+    data: Compressed<T>,
+}
+
+impl<T: Num + Clone + Copy> CsrMatrix<T> {
+    /// Creates a new CSR matrix from its row pointers, column indices and values
+    /// ```
+    /// use mtrs::{CsrMatrix, MatrixError};
+    ///
+    /// let sparse = CsrMatrix::try_from_parts((2, 3), vec![0, 1, 2], vec![2, 0], vec![7, 8]);
+    /// assert_eq!(sparse.unwrap().get((0, 2)), Some(7));
+    ///
+    /// let unsorted = CsrMatrix::try_from_parts((1, 3), vec![0, 2], vec![2, 0], vec![7, 8]);
+    /// assert_eq!(unsorted, Err(MatrixError::InvalidStructure));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::InvalidLength` if `indptr` does not have `height + 1` entries or
+    /// `values` is not as long as `indices`, with `MatrixError::OutOfBounds` if a column is out of
+    /// bounds, and with `MatrixError::InvalidStructure` if `indptr` does not run from `0` up to the
+    /// number of entries, or the columns of a row are not strictly increasing
+    pub fn try_from_parts<S: Size>(
+        size: S,
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, MatrixError> {
+        Ok(CsrMatrix {
+            data: Compressed::try_new(size.dim(), indptr, indices, values)?,
+        })
+    }
+
+    /// Creates a new CSR matrix holding the non-zero entries of a dense matrix
+    pub fn from_dense(matrix: &Matrix<T>) -> Self {
+        CsrMatrix {
+            data: Compressed::from_fn(matrix.size(), |row, col| matrix[(row, col)]),
+        }
+    }
+
+    // Wraps compressed storage whose lines are rows
+    pub(super) fn from_compressed(data: Compressed<T>) -> Self {
+        CsrMatrix { data }
+    }
+
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    pub fn size(&self) -> (usize, usize) {
+        (self.data.outer, self.data.inner)
+    }
+
+    /// The number of stored entries
+    pub fn nnz(&self) -> usize {
+        self.data.values.len()
+    }
+
+    /// The positions in `indices` and `values` where each row starts, followed by `nnz`
+    pub fn indptr(&self) -> &[usize] {
+        &self.data.indptr
+    }
+
+    /// The column of each stored entry
+    pub fn indices(&self) -> &[usize] {
+        &self.data.indices
+    }
+
+    /// The value of each stored entry
+    pub fn values(&self) -> &[T] {
+        &self.data.values
+    }
+
+    /// Returns an entry of the matrix (zero if it is not stored), or `None` if the location is
+    /// out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<T> {
+        let (row, col) = loc.dim();
+        if row < self.data.outer && col < self.data.inner {
+            Some(self.data.get(row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Iterate over the stored entries as `((row, col), value)`, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), T)> + '_ {
+        self.data
+            .entries()
+            .map(|(row, col, value)| ((row, col), value))
+    }
+
+    /// Copy the matrix into a dense `Matrix`
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut dense = Matrix::zeros(self.size());
+        for ((row, col), value) in self.iter() {
+            dense.data[row * self.data.inner + col] = value;
+        }
+
+        dense
+    }
+
+    /// Convert the matrix to coordinate form
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        let mut coo = CooMatrix::new(self.size());
+        for (loc, value) in self.iter() {
+            coo.push(loc, value).unwrap();
+        }
+
+        coo
+    }
+
+    /// Convert the matrix to compressed sparse column form
+    pub fn to_csc(&self) -> CscMatrix<T> {
+        CscMatrix::from_compressed(self.data.transposed())
+    }
+
+    /// Returns the transpose of the matrix as a new matrix
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::CsrMatrix;
+    ///
+    /// let sparse = CsrMatrix::from_dense(&matrix![(2, 3); 1, 0, 2; 0, 3, 0]);
+    ///
+    /// assert_eq!(sparse.transposed().to_dense(), matrix![(3, 2); 1, 0; 0, 3; 2, 0]);
+    /// ```
+    pub fn transposed(&self) -> Self {
+        CsrMatrix {
+            data: self.data.transposed(),
+        }
+    }
+
+    /// Transposes the matrix, see `CsrMatrix::transposed`
+    pub fn transpose(&mut self) {
+        *self = self.transposed();
+    }
+
+    /// Add a sparse matrix of the same size, entry by entry
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_add(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        Ok(CsrMatrix {
+            data: self.data.add(&other.data),
+        })
+    }
+
+    /// Multiply two sparse matrices, where the width of `self` must equal the height of `other`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::CsrMatrix;
+    ///
+    /// let a = matrix![(2, 3); 1, 0, 2; 0, 3, 0];
+    /// let b = matrix![(3, 2); 0, 1; 4, 0; 0, 5];
+    /// let product = CsrMatrix::from_dense(&a).try_mul(&CsrMatrix::from_dense(&b));
+    ///
+    /// assert_eq!(product.unwrap().to_dense(), &a * &b);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul(&self, other: &Self) -> Result<Self, MatrixError> {
+        if self.data.inner != other.data.outer {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        Ok(CsrMatrix {
+            data: self.data.mul(&other.data),
+        })
+    }
+
+    /// Multiply by a dense matrix, where the width of `self` must equal the height of `other`
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul_dense(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.data.inner != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        // Each stored entry adds a multiple of a row of `other` to a row of the product
+        let width = other.width;
+        let mut product = Matrix::zeros((self.data.outer, width));
+        for (row, product_row) in product.rows_mut().enumerate() {
+            for position in self.data.range(row) {
+                let (col, value) = (self.data.indices[position], self.data.values[position]);
+                let other_row = &other.data[col * width..(col + 1) * width];
+                for (entry, &right) in product_row.iter_mut().zip(other_row) {
+                    *entry = *entry + value * right;
+                }
+            }
+        }
+
+        Ok(product)
+    }
+}
+
+impl_sparse_ops!(CsrMatrix);
+
+#[cfg(test)]
+mod csr_tests {
+    use super::CsrMatrix;
+    use crate::sparse::test_sparse_api;
+    use crate::test_util::sparse_sample;
+    use crate::{Matrix, MatrixError};
+
+    test_sparse_api!(CsrMatrix, to_csr, to_csc);
+
+    #[test]
+    fn test_indptr() {
+        let empty = CsrMatrix::from_dense(&Matrix::<i32>::zeros((0, 3)));
+        let sparse = CsrMatrix::from_dense(&Matrix::from_vec((2, 3), vec![0, 1, 2, 0, 0, 3]));
+
+        assert_eq!(empty.indptr(), &[0]);
+        assert_eq!(sparse.indptr(), &[0, 2, 3]);
+        assert_eq!(sparse.indices(), &[1, 2, 2]);
+    }
+}
@@ -0,0 +1,462 @@
+mod coo;
+mod csc;
+mod csr;
+
+pub use coo::CooMatrix;
+pub use csc::CscMatrix;
+pub use csr::CsrMatrix;
+
+use std::ops::Range;
+
+use crate::MatrixError;
+
+use num_traits::Num;
+
+// The storage shared by CSR and CSC matrices. Each of the `outer` lines (rows for CSR, columns for
+// CSC) owns the entries `indptr[line]..indptr[line + 1]` of `indices` and `values`, where the
+// indices along the line are strictly increasing and below `inner`.
+#[derive(Clone, PartialEq, Debug)]
+struct Compressed<T> {
+    outer: usize,
+    inner: usize,
+    indptr: Vec<usize>,
+    indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Num + Clone + Copy> Compressed<T> {
+    // Checks that the arrays describe a valid `outer * inner` structure. Out of bounds indices
+    // are reported as `(line, index)` in a matrix of size `(outer, inner)`.
+    fn try_new(
+        (outer, inner): (usize, usize),
+        indptr: Vec<usize>,
+        indices: Vec<usize>,
+        values: Vec<T>,
+    ) -> Result<Self, MatrixError> {
+        if indptr.len() != outer + 1 {
+            return Err(MatrixError::InvalidLength {
+                expected: outer + 1,
+                found: indptr.len(),
+            });
+        }
+
+        if values.len() != indices.len() {
+            return Err(MatrixError::InvalidLength {
+                expected: indices.len(),
+                found: values.len(),
+            });
+        }
+
+        if indptr[0] != 0
+            || indptr[outer] != indices.len()
+            || indptr.windows(2).any(|pair| pair[0] > pair[1])
+        {
+            return Err(MatrixError::InvalidStructure);
+        }
+
+        for line in 0..outer {
+            let along = &indices[indptr[line]..indptr[line + 1]];
+            if along.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(MatrixError::InvalidStructure);
+            }
+
+            if let Some(&index) = along.last().filter(|&&index| index >= inner) {
+                return Err(MatrixError::OutOfBounds {
+                    index: (line, index),
+                    size: (outer, inner),
+                });
+            }
+        }
+
+        Ok(Compressed {
+            outer,
+            inner,
+            indptr,
+            indices,
+            values,
+        })
+    }
+
+    // Compresses `(line, index, value)` entries in any order, summing repeated positions
+    fn from_entries((outer, inner): (usize, usize), mut entries: Vec<(usize, usize, T)>) -> Self {
+        entries.sort_by_key(|&(line, index, _)| (line, index));
+
+        let mut indptr = vec![0; outer + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(entries.len());
+        let mut values: Vec<T> = Vec::with_capacity(entries.len());
+        let mut last = None;
+        for (line, index, value) in entries {
+            if last == Some((line, index)) {
+                // Repeated positions are adjacent after sorting
+                let sum = values.last_mut().unwrap();
+                *sum = *sum + value;
+            } else {
+                indptr[line + 1] += 1;
+                indices.push(index);
+                values.push(value);
+                last = Some((line, index));
+            }
+        }
+
+        for line in 0..outer {
+            indptr[line + 1] += indptr[line];
+        }
+
+        Compressed {
+            outer,
+            inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    // Compresses the non-zero values of `entry(line, index)`, visiting each line in order
+    fn from_fn<F: Fn(usize, usize) -> T>((outer, inner): (usize, usize), entry: F) -> Self {
+        let mut indptr = Vec::with_capacity(outer + 1);
+        let (mut indices, mut values) = (Vec::new(), Vec::new());
+        indptr.push(0);
+        for line in 0..outer {
+            for index in 0..inner {
+                let value = entry(line, index);
+                if !value.is_zero() {
+                    indices.push(index);
+                    values.push(value);
+                }
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed {
+            outer,
+            inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    // The positions in `indices` and `values` of the entries along a line
+    fn range(&self, line: usize) -> Range<usize> {
+        self.indptr[line]..self.indptr[line + 1]
+    }
+
+    // The value at `(line, index)`, which is zero unless it is stored
+    fn get(&self, line: usize, index: usize) -> T {
+        let range = self.range(line);
+        match self.indices[range.clone()].binary_search(&index) {
+            Ok(offset) => self.values[range.start + offset],
+            Err(_) => T::zero(),
+        }
+    }
+
+    // Every stored entry as `(line, index, value)`, line by line
+    fn entries(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.outer).flat_map(move |line| {
+            self.range(line)
+                .map(move |position| (line, self.indices[position], self.values[position]))
+        })
+    }
+
+    // Swaps the roles of lines and indices with a counting sort, which leaves the indices along
+    // each new line sorted
+    fn transposed(&self) -> Self {
+        let mut indptr = vec![0; self.inner + 1];
+        for &index in &self.indices {
+            indptr[index + 1] += 1;
+        }
+        for index in 0..self.inner {
+            indptr[index + 1] += indptr[index];
+        }
+
+        let mut next = indptr.clone();
+        let mut indices = vec![0; self.indices.len()];
+        let mut values = vec![T::zero(); self.values.len()];
+        for (line, index, value) in self.entries() {
+            indices[next[index]] = line;
+            values[next[index]] = value;
+            next[index] += 1;
+        }
+
+        Compressed {
+            outer: self.inner,
+            inner: self.outer,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    // Adds a structure of the same size, merging the sorted indices of each line
+    fn add(&self, other: &Self) -> Self {
+        let mut indptr = Vec::with_capacity(self.outer + 1);
+        let capacity = self.indices.len() + other.indices.len();
+        let (mut indices, mut values) =
+            (Vec::with_capacity(capacity), Vec::with_capacity(capacity));
+        indptr.push(0);
+        for line in 0..self.outer {
+            let (mut left, mut right) = (self.range(line), other.range(line));
+            loop {
+                let next = match (left.clone().next(), right.clone().next()) {
+                    (Some(l), Some(r)) if self.indices[l] == other.indices[r] => {
+                        left.next();
+                        right.next();
+                        (self.indices[l], self.values[l] + other.values[r])
+                    }
+                    (Some(l), Some(r)) if self.indices[l] < other.indices[r] => {
+                        left.next();
+                        (self.indices[l], self.values[l])
+                    }
+                    (_, Some(r)) => {
+                        right.next();
+                        (other.indices[r], other.values[r])
+                    }
+                    (Some(l), None) => {
+                        left.next();
+                        (self.indices[l], self.values[l])
+                    }
+                    (None, None) => break,
+                };
+                indices.push(next.0);
+                values.push(next.1);
+            }
+            indptr.push(indices.len());
+        }
+
+        Compressed {
+            outer: self.outer,
+            inner: self.inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    // Multiplies by a structure with `self.inner` lines, line by line with a dense accumulator
+    // (Gustavson's algorithm)
+    fn mul(&self, other: &Self) -> Self {
+        let mut sums = vec![T::zero(); other.inner];
+        let mut touched = vec![false; other.inner];
+        let mut line_indices = Vec::new();
+
+        let mut indptr = Vec::with_capacity(self.outer + 1);
+        let (mut indices, mut values) = (Vec::new(), Vec::new());
+        indptr.push(0);
+        for line in 0..self.outer {
+            for position in self.range(line) {
+                let (middle, left) = (self.indices[position], self.values[position]);
+                for other_position in other.range(middle) {
+                    let index = other.indices[other_position];
+                    if !touched[index] {
+                        touched[index] = true;
+                        line_indices.push(index);
+                    }
+                    sums[index] = sums[index] + left * other.values[other_position];
+                }
+            }
+
+            line_indices.sort_unstable();
+            for &index in &line_indices {
+                indices.push(index);
+                values.push(sums[index]);
+                sums[index] = T::zero();
+                touched[index] = false;
+            }
+            line_indices.clear();
+            indptr.push(indices.len());
+        }
+
+        Compressed {
+            outer: self.outer,
+            inner: other.inner,
+            indptr,
+            indices,
+            values,
+        }
+    }
+}
+
+// Implements the operators of a compressed sparse matrix on borrowed operands, panicking on
+// mismatched sizes like the operators of `Matrix`, along with its `Display`
+macro_rules! impl_sparse_ops {
+    ($sparse:ident) => {
+        /// Implements addition between sparse matrices
+        /// # Panics
+        /// Panics if the sizes differ, see `try_add` for a non-panicking version
+        impl<'a, 'b, T: Num + Clone + Copy> Add<&'b $sparse<T>> for &'a $sparse<T> {
+            type Output = $sparse<T>;
+
+            fn add(self, other: &'b $sparse<T>) -> $sparse<T> {
+                self.try_add(other).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        /// Implements multiplication between sparse matrices
+        /// # Panics
+        /// Panics if the inner dimensions differ, see `try_mul` for a non-panicking version
+        impl<'a, 'b, T: Num + Clone + Copy> Mul<&'b $sparse<T>> for &'a $sparse<T> {
+            type Output = $sparse<T>;
+
+            fn mul(self, other: &'b $sparse<T>) -> $sparse<T> {
+                self.try_mul(other).unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        /// Implements multiplication of a dense matrix by a sparse one
+        /// # Panics
+        /// Panics if the inner dimensions differ, see `try_mul_dense` for a non-panicking version
+        impl<'a, 'b, T: Num + Clone + Copy> Mul<&'b Matrix<T>> for &'a $sparse<T> {
+            type Output = Matrix<T>;
+
+            fn mul(self, other: &'b Matrix<T>) -> Matrix<T> {
+                self.try_mul_dense(other)
+                    .unwrap_or_else(|err| panic!("{}", err))
+            }
+        }
+
+        /// Pretty print of the sparse matrix, which prints every entry (including the zeros) the
+        /// same way as `Matrix`
+        impl<T: Num + Clone + Copy + Display> Display for $sparse<T> {
+            fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+                self.to_dense().fmt(fmt)
+            }
+        }
+    };
+}
+
+use impl_sparse_ops;
+
+// Tests the public API shared by `CsrMatrix` and `CscMatrix` against the dense matrices they
+// represent. `$other` is the conversion to the other compressed format.
+#[cfg(test)]
+macro_rules! test_sparse_api {
+    ($sparse:ident, $to_self:ident, $other:ident) => {
+        #[test]
+        fn test_matches_dense() {
+            let a = sparse_sample((6, 9), 1);
+            let (b, c) = (sparse_sample((9, 4), 2), sparse_sample((6, 9), 3));
+            let (sparse_a, sparse_b) = ($sparse::from_dense(&a), $sparse::from_dense(&b));
+            let sparse_c = $sparse::from_dense(&c);
+
+            assert!(sparse_a.nnz() < 6 * 9 / 2);
+            assert_eq!(sparse_a.to_dense(), a);
+            assert_eq!(sparse_a.transposed().to_dense(), a.transposed());
+            assert_eq!((&sparse_a * &sparse_b).to_dense(), &a * &b);
+            assert_eq!(&sparse_a * &b, &a * &b);
+            assert_eq!((&sparse_a + &sparse_c).to_dense(), &a + &c);
+            assert_eq!(sparse_a.$other().to_dense(), a);
+            assert_eq!(sparse_a.$other().$to_self(), sparse_a);
+            assert_eq!(sparse_a.to_coo().$to_self(), sparse_a);
+
+            for row in 0..6 {
+                for col in 0..9 {
+                    assert_eq!(sparse_a.get((row, col)), Some(a[(row, col)]));
+                }
+            }
+            assert_eq!(sparse_a.get((6, 0)), None);
+            assert_eq!(sparse_a.get((0, 9)), None);
+        }
+
+        #[test]
+        fn test_mismatch() {
+            let sparse = $sparse::from_dense(&Matrix::<i32>::zeros((2, 3)));
+
+            assert_eq!(
+                sparse.try_mul(&sparse),
+                Err(MatrixError::DimensionMismatch {
+                    left: (2, 3),
+                    right: (2, 3)
+                })
+            );
+            assert_eq!(
+                sparse.try_mul_dense(&Matrix::zeros((2, 1))),
+                Err(MatrixError::DimensionMismatch {
+                    left: (2, 3),
+                    right: (2, 1)
+                })
+            );
+            assert!(sparse.try_add(&sparse.transposed()).is_err());
+            assert!(sparse.try_add(&sparse.to_coo().$to_self()).is_ok());
+            assert!(sparse.try_mul_dense(&Matrix::zeros((3, 1))).is_ok());
+        }
+
+        #[test]
+        fn test_empty() {
+            let empty = $sparse::from_dense(&Matrix::<i32>::zeros((0, 3)));
+
+            assert_eq!(empty.nnz(), 0);
+            assert_eq!(empty.transposed().size(), (3, 0));
+            assert_eq!((&empty.transposed() * &empty).to_dense(), Matrix::zeros(3));
+            assert_eq!(
+                format!("{}", $sparse::from_dense(&Matrix::<i32>::identity(2))),
+                "1 0 \n0 1 \n"
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+use test_sparse_api;
+
+#[cfg(test)]
+mod sparse_tests {
+    use super::Compressed;
+    use crate::MatrixError;
+
+    #[test]
+    fn test_validation() {
+        let valid = Compressed::try_new((2, 3), vec![0, 1, 3], vec![2, 0, 1], vec![1, 2, 3]);
+        assert!(valid.is_ok());
+
+        let cases: Vec<(Vec<usize>, Vec<usize>, MatrixError)> = vec![
+            (
+                vec![0, 1],
+                vec![0],
+                MatrixError::InvalidLength {
+                    expected: 3,
+                    found: 2,
+                },
+            ),
+            (vec![0, 2, 1], vec![0, 1, 2], MatrixError::InvalidStructure),
+            (vec![0, 1, 2], vec![0, 1, 2], MatrixError::InvalidStructure),
+            (vec![0, 2, 3], vec![1, 1, 0], MatrixError::InvalidStructure),
+            (
+                vec![0, 1, 3],
+                vec![0, 1, 3],
+                MatrixError::OutOfBounds {
+                    index: (1, 3),
+                    size: (2, 3),
+                },
+            ),
+        ];
+
+        for (indptr, indices, err) in cases {
+            let values = vec![1; indices.len()];
+            assert_eq!(
+                Compressed::try_new((2, 3), indptr, indices, values),
+                Err(err)
+            );
+        }
+    }
+
+    #[test]
+    fn test_entries_and_transpose() {
+        // Unsorted, with a repeated position
+        let compressed = Compressed::from_entries(
+            (3, 4),
+            vec![(2, 3, 5), (0, 1, 1), (2, 0, 4), (0, 1, 2), (1, 2, 3)],
+        );
+
+        assert_eq!(compressed.indptr, vec![0, 1, 2, 4]);
+        assert_eq!(compressed.indices, vec![1, 2, 0, 3]);
+        assert_eq!(compressed.values, vec![3, 3, 4, 5]);
+        assert_eq!(compressed.get(2, 3), 5);
+        assert_eq!(compressed.get(2, 2), 0);
+
+        let transposed = compressed.transposed();
+        assert_eq!(transposed.indptr, vec![0, 1, 2, 3, 4]);
+        assert_eq!(transposed.indices, vec![2, 0, 1, 2]);
+        assert_eq!(transposed.values, vec![4, 3, 3, 5]);
+        assert_eq!(transposed.transposed(), compressed);
+    }
+}
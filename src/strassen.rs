@@ -0,0 +1,250 @@
+use crate::gemm;
+use crate::parallel::Element;
+use crate::{Matrix, MatrixError, MatrixView, MatrixViewMut};
+
+use num_traits::Num;
+
+/// The size below which `Matrix::mul_strassen` multiplies blocks with the tiled kernel of `*`
+/// instead of splitting them further
+pub const STRASSEN_THRESHOLD: usize = 128;
+
+impl<T: Num + Clone + Copy + Element> Matrix<T> {
+    /// Multiply two matrices with the Strassen-Winograd algorithm, which takes 7 rather than 8
+    /// half size products at each level. Blocks with any dimension at or below
+    /// `STRASSEN_THRESHOLD` are multiplied with the same kernel as `*`, and odd rows and columns
+    /// are peeled off and multiplied separately, so any compatible sizes work.
+    ///
+    /// This pays off for large, roughly square matrices. Integer products are exact (unless an
+    /// intermediate sum overflows, which includes differences of unsigned entries), while float
+    /// products are rounded differently from `*` and are somewhat less accurate.
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let m1 = matrix![(2, 3); 2, 3, 4; 1, 0, 0];
+    /// let m2 = matrix![(3, 2); 0, 1000; 1, 100; 0, 10];
+    ///
+    /// assert_eq!(m1.mul_strassen(&m2), Ok(matrix![(2, 2); 3, 2340; 0, 1000]));
+    /// assert!(m1.mul_strassen(&m1).is_err());
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn mul_strassen(&self, other: &Self) -> Result<Self, MatrixError> {
+        self.mul_strassen_with(other, STRASSEN_THRESHOLD)
+    }
+
+    /// Multiply two matrices with the Strassen-Winograd algorithm, see `Matrix::mul_strassen`,
+    /// with a custom crossover `threshold` at or below which blocks are multiplied directly. A
+    /// threshold of `0` behaves like `1`.
+    /// ```
+    /// use mtrs::Matrix;
+    ///
+    /// let matrix = Matrix::from_vec(5, (0..25).collect());
+    ///
+    /// assert_eq!(matrix.mul_strassen_with(&matrix, 1), matrix.try_mul(&matrix));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn mul_strassen_with(&self, other: &Self, threshold: usize) -> Result<Self, MatrixError> {
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let mut product = Self::zeros((self.height, other.width));
+        strassen(
+            &self.as_view(),
+            &other.as_view(),
+            &mut product.as_view_mut(),
+            threshold,
+        );
+        Ok(product)
+    }
+}
+
+// Overwrites `c` with `a * b`, peeling off the last row and column of odd sizes so that the rest
+// splits evenly into quadrants
+fn strassen<T: Num + Clone + Copy + Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    c: &mut MatrixViewMut<T>,
+    threshold: usize,
+) {
+    let ((height, depth), width) = (a.size(), b.size().1);
+    if height.min(depth).min(width) <= threshold.max(1) {
+        gemm::kernel(T::one(), a, b, T::zero(), c);
+        return;
+    }
+
+    let (even_height, even_depth, even_width) = (height & !1, depth & !1, width & !1);
+    winograd(
+        &a.view(..even_height, ..even_depth),
+        &b.view(..even_depth, ..even_width),
+        &mut c.view_mut(..even_height, ..even_width),
+        threshold,
+    );
+
+    if even_depth < depth {
+        gemm::kernel(
+            T::one(),
+            &a.view(..even_height, even_depth..),
+            &b.view(even_depth.., ..even_width),
+            T::one(),
+            &mut c.view_mut(..even_height, ..even_width),
+        );
+    }
+
+    if even_width < width {
+        gemm::kernel(
+            T::one(),
+            &a.view(..even_height, ..),
+            &b.view(.., even_width..),
+            T::zero(),
+            &mut c.view_mut(..even_height, even_width..),
+        );
+    }
+
+    if even_height < height {
+        gemm::kernel(
+            T::one(),
+            &a.view(even_height.., ..),
+            b,
+            T::zero(),
+            &mut c.view_mut(even_height.., ..),
+        );
+    }
+}
+
+// One level of the Winograd form of Strassen's algorithm, for even sizes. It uses 7 products of
+// quadrants and 15 additions, and each product recurses through `strassen`.
+fn winograd<T: Num + Clone + Copy + Element>(
+    a: &MatrixView<T>,
+    b: &MatrixView<T>,
+    c: &mut MatrixViewMut<T>,
+    threshold: usize,
+) {
+    let ((height, depth), width) = (a.size(), b.size().1);
+    let (h, d, w) = (height / 2, depth / 2, width / 2);
+
+    let (a11, a12) = (a.view(..h, ..d), a.view(..h, d..));
+    let (a21, a22) = (a.view(h.., ..d), a.view(h.., d..));
+    let (b11, b12) = (b.view(..d, ..w), b.view(..d, w..));
+    let (b21, b22) = (b.view(d.., ..w), b.view(d.., w..));
+
+    let s1 = sum(&a21, &a22);
+    let s2 = difference(&s1.as_view(), &a11);
+    let s3 = difference(&a11, &a21);
+    let s4 = difference(&a12, &s2.as_view());
+    let t1 = difference(&b12, &b11);
+    let t2 = difference(&b22, &t1.as_view());
+    let t3 = difference(&b22, &b12);
+    let t4 = difference(&t2.as_view(), &b21);
+
+    let product = |left: &MatrixView<T>, right: &MatrixView<T>| {
+        let mut out = Matrix::zeros((left.size().0, right.size().1));
+        strassen(left, right, &mut out.as_view_mut(), threshold);
+        out
+    };
+
+    let m1 = product(&a11, &b11);
+    let m2 = product(&a12, &b21);
+    let m3 = product(&s4.as_view(), &b22);
+    let m4 = product(&a22, &t4.as_view());
+    let m5 = product(&s1.as_view(), &t1.as_view());
+    let m6 = product(&s2.as_view(), &t2.as_view());
+    let m7 = product(&s3.as_view(), &t3.as_view());
+
+    let u2 = m1.clone() + &m6;
+    let u3 = u2.clone() + &m7;
+    let u4 = u2 + &m5;
+
+    write_block(c, (0, 0), m1 + &m2);
+    write_block(c, (0, w), u4 + &m3);
+    write_block(c, (h, 0), u3.clone() - &m4);
+    write_block(c, (h, w), u3 + &m5);
+}
+
+// Entrywise sum of two views of the same size
+fn sum<T: Num + Clone + Copy + Element>(left: &MatrixView<T>, right: &MatrixView<T>) -> Matrix<T> {
+    // The quadrants always have matching sizes
+    left.try_add(right).unwrap()
+}
+
+// Entrywise difference of two views of the same size
+fn difference<T: Num + Clone + Copy + Element>(
+    left: &MatrixView<T>,
+    right: &MatrixView<T>,
+) -> Matrix<T> {
+    // The quadrants always have matching sizes
+    left.try_sub(right).unwrap()
+}
+
+// Copies `block` into `c` with its top left entry at `(row, col)`
+fn write_block<T: Num + Clone + Copy>(
+    c: &mut MatrixViewMut<T>,
+    (row, col): (usize, usize),
+    block: Matrix<T>,
+) {
+    let (height, width) = block.size();
+    c.view_mut(row..row + height, col..col + width)
+        .copy_from(&block.as_view())
+        // The block was sized to fit the quadrant
+        .unwrap();
+}
+
+#[cfg(test)]
+mod strassen_tests {
+    use crate::test_util::sample;
+    use crate::{Matrix, MatrixError};
+
+    #[test]
+    fn test_exact_integers() {
+        // Powers of two, odd sizes at several levels, and rectangular shapes
+        for &(height, depth, width) in &[(16, 16, 16), (13, 13, 13), (37, 21, 30), (1, 40, 2)] {
+            let a = sample((height, depth), 1);
+            let b = sample((depth, width), 2);
+            let expected = a.try_mul(&b);
+
+            for &threshold in &[0, 1, 2, 5, 64] {
+                assert_eq!(a.mul_strassen_with(&b, threshold), expected);
+            }
+        }
+
+        let a = sample((200, 200), 3);
+        assert_eq!(a.mul_strassen(&a), a.try_mul(&a));
+    }
+
+    #[test]
+    fn test_floats() {
+        let a: Matrix<f64> = Matrix::from_vec(
+            33,
+            (0..33 * 33)
+                .map(|i| ((i * 37) % 17) as f64 / 17.0)
+                .collect(),
+        );
+        let product = a.mul_strassen_with(&a, 4).unwrap();
+        let expected = &a * &a;
+
+        for (got, want) in product.iter().zip(expected.iter()) {
+            assert!((got - want).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let empty = Matrix::<i32>::zeros((3, 0));
+
+        assert_eq!(
+            empty.mul_strassen_with(&Matrix::zeros((0, 3)), 0),
+            Ok(Matrix::zeros(3))
+        );
+        assert_eq!(
+            Matrix::<i32>::zeros((2, 3)).mul_strassen(&Matrix::zeros(2)),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 2)
+            })
+        );
+    }
+}
@@ -0,0 +1,27 @@
+// Fixtures shared by the unit tests
+
+use crate::Matrix;
+
+// A `height * width` matrix of small integers in `-11..=11`, scattered by a multiplicative hash of
+// their index. Different seeds give unrelated matrices of the same size.
+pub(crate) fn sample((height, width): (usize, usize), seed: i64) -> Matrix<i64> {
+    Matrix::from_vec(
+        (height, width),
+        (0..(height * width) as i64)
+            .map(|i| (i * 7919 + seed) % 23 - 11)
+            .collect(),
+    )
+}
+
+// As `sample`, but mostly zeros, with a few scattered entries in `-2..=2`
+pub(crate) fn sparse_sample(size: (usize, usize), seed: i64) -> Matrix<i64> {
+    let dense = sample(size, seed);
+    Matrix::from_vec(
+        size,
+        dense
+            .as_slice()
+            .iter()
+            .map(|&entry| if entry.abs() <= 2 { entry } else { 0 })
+            .collect(),
+    )
+}
@@ -0,0 +1,782 @@
+use std::ops::Range;
+
+use crate::determinant::Determinant;
+use crate::gemm;
+use crate::inverse::Inverse;
+use crate::parallel::Element;
+use crate::size::{Size, SliceRange};
+use crate::{Cholesky, Layout, Matrix, MatrixError, Schur, SymmetricEigen, LDLT, LU, QR, SVD};
+
+use num_traits::{Float, Num};
+
+/// A borrowed, read-only window into the entries of a `Matrix`.
+///
+/// Entry `(i, j)` of the view lives at `i * row_stride + j * col_stride` in the borrowed data, so
+/// views can select a block of rows and columns, or read the matrix transposed, without copying.
+/// Arithmetic and decompositions on a view produce an owned `Matrix`.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let matrix = matrix![(3, 3); 1, 2, 3; 4, 5, 6; 7, 8, 9];
+/// let block = matrix.view(1..3, 0..2);
+///
+/// assert_eq!(block.size(), (2, 2));
+/// assert_eq!(block[(1, 1)], 8);
+/// assert_eq!(block.to_matrix(), matrix![(2, 2); 4, 5; 7, 8]);
+/// assert_eq!(matrix.transpose_view()[(0, 2)], 7);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct MatrixView<'a, T: Num> {
+    /// The borrowed entries, starting at entry `(0, 0)` of the view
+    data: &'a [T],
+
+    /// The height of the view
+    height: usize,
+
+    /// The width of the view
+    width: usize,
+
+    /// The distance in `data` between two vertically adjacent entries
+    row_stride: usize,
+
+    /// The distance in `data` between two horizontally adjacent entries
+    col_stride: usize,
+}
+
+/// A borrowed, mutable window into the entries of a `Matrix`, see `MatrixView`.
+/// Writing through the view writes to the matrix it borrows from.
+/// ```
+/// #[macro_use] extern crate mtrs;
+///
+/// let mut matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+/// matrix.col_mut(1).fill(0);
+/// matrix.view_mut(0..1, 0..3)[(0, 2)] = 9;
+///
+/// assert_eq!(matrix, matrix![(2, 3); 1, 0, 9; 4, 0, 6]);
+/// ```
+#[derive(Debug)]
+pub struct MatrixViewMut<'a, T: Num> {
+    /// The borrowed entries, starting at entry `(0, 0)` of the view
+    data: &'a mut [T],
+
+    /// The height of the view
+    height: usize,
+
+    /// The width of the view
+    width: usize,
+
+    /// The distance in `data` between two vertically adjacent entries
+    row_stride: usize,
+
+    /// The distance in `data` between two horizontally adjacent entries
+    col_stride: usize,
+}
+
+// Resolves a block of rows and columns against a `height * width` view, or `None` if the block
+// does not lie inside the view (including when its end overflows)
+fn resolve_block<R: SliceRange, C: SliceRange>(
+    (height, width): (usize, usize),
+    rows: R,
+    cols: C,
+) -> Option<(Range<usize>, Range<usize>)> {
+    let (rows, cols) = (rows.range(height)?, cols.range(width)?);
+    if rows.start <= rows.end && rows.end <= height && cols.start <= cols.end && cols.end <= width {
+        Some((rows, cols))
+    } else {
+        None
+    }
+}
+
+// As `resolve_block`, but panics if the block does not lie inside the view
+fn expect_block<R: SliceRange, C: SliceRange>(
+    size: (usize, usize),
+    rows: R,
+    cols: C,
+) -> (Range<usize>, Range<usize>) {
+    resolve_block(size, rows, cols).unwrap_or_else(|| {
+        panic!(
+            "the block is out of bounds for a {}x{} matrix",
+            size.0, size.1
+        )
+    })
+}
+
+// The range of the underlying data spanned by a (valid) block of a view with the given strides
+fn block_span(
+    (row_stride, col_stride): (usize, usize),
+    rows: &Range<usize>,
+    cols: &Range<usize>,
+) -> Range<usize> {
+    if rows.start == rows.end || cols.start == cols.end {
+        return 0..0;
+    }
+
+    let first = rows.start * row_stride + cols.start * col_stride;
+    let last = (rows.end - 1) * row_stride + (cols.end - 1) * col_stride;
+    first..last + 1
+}
+
+impl<T: Num + Clone + Copy, L: Layout> Matrix<T, L> {
+    /// Borrow the whole matrix as a `MatrixView`
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        let (row_stride, col_stride) = L::strides(self.height, self.width);
+        MatrixView {
+            data: &self.data,
+            height: self.height,
+            width: self.width,
+            row_stride,
+            col_stride,
+        }
+    }
+
+    /// Borrow the whole matrix as a `MatrixViewMut`
+    pub fn as_view_mut(&mut self) -> MatrixViewMut<'_, T> {
+        let (row_stride, col_stride) = L::strides(self.height, self.width);
+        MatrixViewMut {
+            data: &mut self.data,
+            height: self.height,
+            width: self.width,
+            row_stride,
+            col_stride,
+        }
+    }
+
+    /// Borrow the block of the matrix made of the given rows and columns, each of which can be
+    /// a range (including `..`) or a single index
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    ///
+    /// assert_eq!(matrix.view(0..2, 1..3).to_matrix(), matrix![(2, 2); 2, 3; 5, 6]);
+    /// assert_eq!(matrix.view(.., 1..).to_matrix(), matrix![(2, 2); 2, 3; 5, 6]);
+    /// assert_eq!(matrix.view(1, ..=1).to_matrix(), matrix![(1, 2); 4, 5]);
+    /// ```
+    /// # Panics
+    /// Panics if the block does not lie inside the matrix
+    pub fn view<R: SliceRange, C: SliceRange>(&self, rows: R, cols: C) -> MatrixView<'_, T> {
+        self.as_view().view(rows, cols)
+    }
+
+    /// Borrow the block of the matrix made of the given rows and columns, or `None` if the block
+    /// does not lie inside the matrix
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    ///
+    /// assert!(matrix.get_block(1.., ..).is_some());
+    /// assert!(matrix.get_block(1..3, ..).is_none());
+    /// ```
+    pub fn get_block<R: SliceRange, C: SliceRange>(
+        &self,
+        rows: R,
+        cols: C,
+    ) -> Option<MatrixView<'_, T>> {
+        self.as_view().get_block(rows, cols)
+    }
+
+    /// Mutably borrow the block of the matrix made of the given rows and columns, each of which
+    /// can be a range (including `..`) or a single index
+    /// # Panics
+    /// Panics if the block does not lie inside the matrix
+    pub fn view_mut<R: SliceRange, C: SliceRange>(
+        &mut self,
+        rows: R,
+        cols: C,
+    ) -> MatrixViewMut<'_, T> {
+        let (rows, cols) = expect_block(self.size(), rows, cols);
+        self.as_view_mut().into_view_mut(rows, cols)
+    }
+
+    /// Borrow a single row of the matrix, as a `1 * width` view
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    ///
+    /// assert_eq!(matrix.row(1).to_matrix(), matrix![(1, 3); 4, 5, 6]);
+    /// assert_eq!(matrix.col(2).to_matrix(), matrix![(2, 1); 3; 6]);
+    /// ```
+    /// # Panics
+    /// Panics if the row is out of bounds
+    pub fn row(&self, index: usize) -> MatrixView<'_, T> {
+        self.view(index, ..)
+    }
+
+    /// Mutably borrow a single row of the matrix, as a `1 * width` view
+    /// # Panics
+    /// Panics if the row is out of bounds
+    pub fn row_mut(&mut self, index: usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(index, ..)
+    }
+
+    /// Borrow a single column of the matrix, as a `height * 1` view
+    /// # Panics
+    /// Panics if the column is out of bounds
+    pub fn col(&self, index: usize) -> MatrixView<'_, T> {
+        self.view(.., index)
+    }
+
+    /// Mutably borrow a single column of the matrix, as a `height * 1` view
+    /// # Panics
+    /// Panics if the column is out of bounds
+    pub fn col_mut(&mut self, index: usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(.., index)
+    }
+
+    /// Borrow the matrix transposed, without moving any entries
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    ///
+    /// assert_eq!(matrix.transpose_view().to_matrix(), matrix![(3, 2); 1, 4; 2, 5; 3, 6]);
+    /// ```
+    pub fn transpose_view(&self) -> MatrixView<'_, T> {
+        self.as_view().transpose_view()
+    }
+}
+
+impl<'a, T: Num + Clone + Copy> MatrixView<'a, T> {
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Returns an entry in the view safely, or `None` if it is out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<&'a T> {
+        let (h, w) = loc.dim();
+        if h < self.height && w < self.width {
+            Some(&self.data[h * self.row_stride + w * self.col_stride])
+        } else {
+            None
+        }
+    }
+
+    // Borrows `height * width` entries stored row by row
+    pub(crate) fn from_row_major(data: &'a [T], (height, width): (usize, usize)) -> Self {
+        MatrixView {
+            data,
+            height,
+            width,
+            row_stride: width,
+            col_stride: 1,
+        }
+    }
+
+    // The entry at `(row, col)`, which must be in bounds
+    pub(crate) fn at(&self, row: usize, col: usize) -> T {
+        self.data[row * self.row_stride + col * self.col_stride]
+    }
+
+    /// Copy the entries of the view into a new `Matrix`
+    pub fn to_matrix(&self) -> Matrix<T> {
+        let mut body = Vec::with_capacity(self.height * self.width);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                body.push(self.at(row, col));
+            }
+        }
+
+        Matrix::from_vec(self.size(), body)
+    }
+
+    /// Borrow a block of the view, made of the given rows and columns
+    /// # Panics
+    /// Panics if the block does not lie inside the view
+    pub fn view<R: SliceRange, C: SliceRange>(&self, rows: R, cols: C) -> MatrixView<'a, T> {
+        let (rows, cols) = expect_block(self.size(), rows, cols);
+        self.into_view(rows, cols)
+    }
+
+    /// Borrow a block of the view, or `None` if the block does not lie inside the view
+    pub fn get_block<R: SliceRange, C: SliceRange>(
+        &self,
+        rows: R,
+        cols: C,
+    ) -> Option<MatrixView<'a, T>> {
+        resolve_block(self.size(), rows, cols).map(|(rows, cols)| self.into_view(rows, cols))
+    }
+
+    // Shrinks the view to a (valid) block, keeping the original lifetime
+    fn into_view(self, rows: Range<usize>, cols: Range<usize>) -> MatrixView<'a, T> {
+        let span = block_span((self.row_stride, self.col_stride), &rows, &cols);
+
+        MatrixView {
+            data: &self.data[span],
+            height: rows.end - rows.start,
+            width: cols.end - cols.start,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// Borrow a single row of the view
+    /// # Panics
+    /// Panics if the row is out of bounds
+    pub fn row(&self, index: usize) -> MatrixView<'a, T> {
+        self.view(index, ..)
+    }
+
+    /// Borrow a single column of the view
+    /// # Panics
+    /// Panics if the column is out of bounds
+    pub fn col(&self, index: usize) -> MatrixView<'a, T> {
+        self.view(.., index)
+    }
+
+    /// The view transposed, which swaps the strides rather than moving any entries
+    pub fn transpose_view(&self) -> MatrixView<'a, T> {
+        MatrixView {
+            data: self.data,
+            height: self.width,
+            width: self.height,
+            row_stride: self.col_stride,
+            col_stride: self.row_stride,
+        }
+    }
+
+    /// Add a view of the same size, entry by entry
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_add(&self, other: &MatrixView<T>) -> Result<Matrix<T>, MatrixError> {
+        self.zip_with(other, |left, right| left + right)
+    }
+
+    /// Subtract a view of the same size, entry by entry
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn try_sub(&self, other: &MatrixView<T>) -> Result<Matrix<T>, MatrixError> {
+        self.zip_with(other, |left, right| left - right)
+    }
+
+    // Combines two views of the same size entry by entry
+    fn zip_with<F: Fn(T, T) -> T>(
+        &self,
+        other: &MatrixView<T>,
+        f: F,
+    ) -> Result<Matrix<T>, MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        Ok(self.map(|row, col, x| f(x, other.at(row, col))))
+    }
+
+    // Builds a matrix of the same size from each entry and its location
+    fn map<F: Fn(usize, usize, T) -> T>(&self, f: F) -> Matrix<T> {
+        let mut body = Vec::with_capacity(self.height * self.width);
+        for row in 0..self.height {
+            for col in 0..self.width {
+                body.push(f(row, col, self.at(row, col)));
+            }
+        }
+
+        Matrix::from_vec(self.size(), body)
+    }
+
+    /// Add a scalar constant to every entry of the view
+    pub fn scalar_add(&self, value: T) -> Matrix<T> {
+        self.map(|_, _, x| x + value)
+    }
+
+    /// Subtract a scalar constant from every entry of the view
+    pub fn scalar_sub(&self, value: T) -> Matrix<T> {
+        self.map(|_, _, x| x - value)
+    }
+
+    /// Multiply every entry of the view by a scalar constant
+    pub fn scalar_mul(&self, value: T) -> Matrix<T> {
+        self.map(|_, _, x| x * value)
+    }
+
+    /// Divide every entry of the view by a scalar constant
+    pub fn scalar_div(&self, value: T) -> Matrix<T> {
+        self.map(|_, _, x| x / value)
+    }
+}
+
+impl<'a, T: Num + Clone + Copy + Element> MatrixView<'a, T> {
+    /// Multiply two views, where the width of `self` must equal the height of `other`
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    ///
+    /// let matrix = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    /// let gram = matrix.as_view().try_mul(&matrix.transpose_view());
+    ///
+    /// assert_eq!(gram, Ok(matrix![(2, 2); 14, 32; 32, 77]));
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the inner dimensions differ
+    pub fn try_mul(&self, other: &MatrixView<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.width != other.height {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        let mut product = Matrix::zeros((self.height, other.width));
+        gemm::kernel(T::one(), self, other, T::zero(), &mut product.as_view_mut());
+        Ok(product)
+    }
+}
+
+impl<'a, T: Num + Clone + Copy> MatrixViewMut<'a, T> {
+    /// Returns a tuple representing the dimensions (`(height, width)`)
+    pub fn size(&self) -> (usize, usize) {
+        (self.height, self.width)
+    }
+
+    /// Reborrow as a read-only `MatrixView`, which has the arithmetic and decompositions
+    pub fn as_view(&self) -> MatrixView<'_, T> {
+        MatrixView {
+            data: &*self.data,
+            height: self.height,
+            width: self.width,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// Copy the entries of the view into a new `Matrix`
+    pub fn to_matrix(&self) -> Matrix<T> {
+        self.as_view().to_matrix()
+    }
+
+    /// Returns an entry in the view safely, or `None` if it is out of bounds
+    pub fn get<S: Size>(&self, loc: S) -> Option<&T> {
+        let (h, w) = loc.dim();
+        if h < self.height && w < self.width {
+            Some(&self.data[h * self.row_stride + w * self.col_stride])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to an entry in the view, or `None` if it is out of bounds
+    pub fn get_mut<S: Size>(&mut self, loc: S) -> Option<&mut T> {
+        let (h, w) = loc.dim();
+        if h < self.height && w < self.width {
+            Some(&mut self.data[h * self.row_stride + w * self.col_stride])
+        } else {
+            None
+        }
+    }
+
+    // Mutably borrows `height * width` entries stored row by row
+    pub(crate) fn from_row_major(data: &'a mut [T], (height, width): (usize, usize)) -> Self {
+        MatrixViewMut {
+            data,
+            height,
+            width,
+            row_stride: width,
+            col_stride: 1,
+        }
+    }
+
+    // A mutable reference to the entry at `(row, col)`, which must be in bounds
+    pub(crate) fn at_mut(&mut self, row: usize, col: usize) -> &mut T {
+        &mut self.data[row * self.row_stride + col * self.col_stride]
+    }
+
+    // Splits the view into the rows above `row` and the rest, or `None` if the rows of the view are
+    // interleaved in the borrowed data (as when it is transposed) so that they cannot be separated
+    #[cfg(feature = "rayon")]
+    pub(crate) fn split_rows_mut(
+        &mut self,
+        row: usize,
+    ) -> Option<(MatrixViewMut<'_, T>, MatrixViewMut<'_, T>)> {
+        let (height, width) = self.size();
+        let (row_stride, col_stride) = (self.row_stride, self.col_stride);
+        if row > height || col_stride * width.saturating_sub(1) >= row_stride.max(1) {
+            return None;
+        }
+
+        let split = (row * row_stride).min(self.data.len());
+        let (top, bottom) = self.data.split_at_mut(split);
+        let part = |data, height| MatrixViewMut {
+            data,
+            height,
+            width,
+            row_stride,
+            col_stride,
+        };
+        Some((part(top, row), part(bottom, height - row)))
+    }
+
+    /// Sets an entry in the view, and so in the underlying matrix
+    /// # Failure
+    /// Fails with `MatrixError::OutOfBounds` if the location is out of bounds
+    pub fn set<S: Size>(&mut self, loc: S, val: T) -> Result<(), MatrixError> {
+        let size = self.size();
+        let index = loc.dim();
+        match self.get_mut(loc) {
+            Some(entry) => {
+                *entry = val;
+                Ok(())
+            }
+            None => Err(MatrixError::OutOfBounds { index, size }),
+        }
+    }
+
+    /// Mutably borrow a block of the view, made of the given rows and columns
+    /// # Panics
+    /// Panics if the block does not lie inside the view
+    pub fn view_mut<R: SliceRange, C: SliceRange>(
+        &mut self,
+        rows: R,
+        cols: C,
+    ) -> MatrixViewMut<'_, T> {
+        let (rows, cols) = expect_block(self.size(), rows, cols);
+        MatrixViewMut {
+            data: &mut *self.data,
+            height: self.height,
+            width: self.width,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+        .into_view_mut(rows, cols)
+    }
+
+    // Shrinks the view to a (valid) block, keeping the original lifetime
+    fn into_view_mut(self, rows: Range<usize>, cols: Range<usize>) -> MatrixViewMut<'a, T> {
+        let span = block_span((self.row_stride, self.col_stride), &rows, &cols);
+
+        MatrixViewMut {
+            data: &mut self.data[span],
+            height: rows.end - rows.start,
+            width: cols.end - cols.start,
+            row_stride: self.row_stride,
+            col_stride: self.col_stride,
+        }
+    }
+
+    /// Mutably borrow a single row of the view
+    /// # Panics
+    /// Panics if the row is out of bounds
+    pub fn row_mut(&mut self, index: usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(index, ..)
+    }
+
+    /// Mutably borrow a single column of the view
+    /// # Panics
+    /// Panics if the column is out of bounds
+    pub fn col_mut(&mut self, index: usize) -> MatrixViewMut<'_, T> {
+        self.view_mut(.., index)
+    }
+
+    /// Set every entry of the view to `value`
+    pub fn fill(&mut self, value: T) {
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.data[row * self.row_stride + col * self.col_stride] = value;
+            }
+        }
+    }
+
+    /// Overwrite the entries of the view with those of another view of the same size
+    /// ```
+    /// #[macro_use] extern crate mtrs;
+    /// use mtrs::Matrix;
+    ///
+    /// let mut matrix: Matrix<i32> = Matrix::zeros(3);
+    /// let source = matrix![(2, 2); 1, 2; 3, 4];
+    /// matrix.view_mut(1..3, 1..3).copy_from(&source.as_view()).unwrap();
+    ///
+    /// assert_eq!(matrix, matrix![(3, 3); 0, 0, 0; 0, 1, 2; 0, 3, 4]);
+    /// ```
+    /// # Failure
+    /// Fails with `MatrixError::DimensionMismatch` if the sizes differ
+    pub fn copy_from(&mut self, other: &MatrixView<T>) -> Result<(), MatrixError> {
+        if self.size() != other.size() {
+            return Err(MatrixError::DimensionMismatch {
+                left: self.size(),
+                right: other.size(),
+            });
+        }
+
+        for row in 0..self.height {
+            for col in 0..self.width {
+                self.data[row * self.row_stride + col * self.col_stride] = other.at(row, col);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Forwards methods of `Matrix` to a copy of the view, for algorithms that work on owned storage
+macro_rules! delegate {
+    ($($name:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {$(
+        #[doc = concat!("See `Matrix::", stringify!($name), "`, which this calls on a copy of the view")]
+        pub fn $name(&self $(, $arg: $ty)*) -> $ret {
+            self.to_matrix().$name($($arg),*)
+        }
+    )*};
+}
+
+impl<'a, T: Determinant> MatrixView<'a, T> {
+    delegate! {
+        determinant() -> Option<T>;
+        try_determinant() -> Result<T, MatrixError>;
+        checked_determinant() -> Result<T, MatrixError>;
+    }
+}
+
+impl<'a, T: Inverse> MatrixView<'a, T> {
+    delegate! {
+        inverse() -> Result<Matrix<T>, MatrixError>;
+    }
+}
+
+impl<'a, T: Float + Element> MatrixView<'a, T> {
+    delegate! {
+        solve(b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>;
+        solve_vec(b: &[T]) -> Result<Vec<T>, MatrixError>;
+        lu() -> Result<LU<T>, MatrixError>;
+        qr() -> QR<T>;
+        least_squares(b: &Matrix<T>) -> Result<Matrix<T>, MatrixError>;
+        cholesky() -> Result<Cholesky<T>, MatrixError>;
+        ldlt() -> Result<LDLT<T>, MatrixError>;
+        svd() -> Result<SVD<T>, MatrixError>;
+        svd_full() -> Result<SVD<T>, MatrixError>;
+        singular_values() -> Result<Vec<T>, MatrixError>;
+        rank(tol: T) -> Result<usize, MatrixError>;
+        pinv() -> Result<Matrix<T>, MatrixError>;
+        norm2() -> Result<T, MatrixError>;
+        condition_number() -> Result<T, MatrixError>;
+        symmetric_eigen() -> Result<SymmetricEigen<T>, MatrixError>;
+        schur() -> Result<Schur<T>, MatrixError>;
+    }
+
+    #[cfg(feature = "complex")]
+    delegate! {
+        eigenvalues() -> Result<Vec<num_complex::Complex<T>>, MatrixError>;
+        eigen() -> Result<crate::Eigen<T>, MatrixError>;
+    }
+}
+
+#[cfg(test)]
+mod view_tests {
+    use crate::{Matrix, MatrixError};
+
+    fn sample() -> Matrix<i32> {
+        Matrix::from_vec((3, 4), (1..=12).collect())
+    }
+
+    #[test]
+    fn test_views() {
+        let matrix = sample();
+        let block = matrix.view(1..3, 1..4);
+
+        assert_eq!(block.size(), (2, 3));
+        assert_eq!(
+            block.to_matrix(),
+            Matrix::from_vec((2, 3), vec![6, 7, 8, 10, 11, 12])
+        );
+        assert_eq!(
+            block.row(1).to_matrix(),
+            Matrix::from_vec((1, 3), vec![10, 11, 12])
+        );
+        assert_eq!(
+            block.col(0).to_matrix(),
+            Matrix::from_vec((2, 1), vec![6, 10])
+        );
+        assert_eq!(block.get((2, 0)), None);
+
+        // Views of views keep the strides of the original matrix
+        let transposed = block.transpose_view();
+        assert_eq!(transposed.size(), (3, 2));
+        assert_eq!(
+            transposed.view(1..3, 1..2).to_matrix(),
+            Matrix::from_vec((2, 1), vec![11, 12])
+        );
+        assert_eq!(matrix.transpose_view().transpose_view(), matrix.as_view());
+
+        let empty = matrix.view(3..3, 0..4);
+        assert_eq!(empty.size(), (0, 4));
+        assert_eq!(empty.to_matrix(), Matrix::zeros((0, 4)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_view_out_of_bounds() {
+        sample().view(0..2, 2..5);
+    }
+
+    #[test]
+    fn test_mutable_views() {
+        let mut matrix = sample();
+        {
+            let mut block = matrix.view_mut(0..2, 2..4);
+            block[(0, 0)] = 0;
+            block.col_mut(1).fill(-1);
+            assert_eq!(
+                block.set((2, 0), 5),
+                Err(MatrixError::OutOfBounds {
+                    index: (2, 0),
+                    size: (2, 2)
+                })
+            );
+        }
+        matrix
+            .row_mut(2)
+            .copy_from(&Matrix::ones((1, 4)).as_view())
+            .unwrap();
+
+        assert_eq!(
+            matrix,
+            Matrix::from_vec((3, 4), vec![1, 2, 0, -1, 5, 6, 7, -1, 1, 1, 1, 1])
+        );
+        assert!(matrix.col_mut(0).copy_from(&sample().row(0)).is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let matrix = sample();
+        let left = matrix.view(0..2, 0..2);
+        let right = matrix.view(1..3, 2..4);
+
+        assert_eq!(
+            left.try_add(&right),
+            Ok(Matrix::from_vec(2, vec![8, 10, 16, 18]))
+        );
+        assert_eq!(
+            left.try_sub(&right),
+            Ok(Matrix::from_vec(2, vec![-6, -6, -6, -6]))
+        );
+        assert_eq!(
+            left.try_mul(&matrix.view(0..2, 0..1)),
+            Ok(Matrix::from_vec((2, 1), vec![11, 35]))
+        );
+        assert_eq!(
+            left.try_mul(&matrix.transpose_view()),
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 2),
+                right: (4, 3)
+            })
+        );
+        assert_eq!(left.scalar_mul(2), Matrix::from_vec(2, vec![2, 4, 10, 12]));
+        assert_eq!(left.determinant(), Some(-4));
+    }
+
+    #[test]
+    fn test_decompositions() {
+        let matrix = Matrix::from_vec(3, vec![9.0, 4.0, 2.0, 0.0, 4.0, 2.0, 1.0, 2.0, 3.0]);
+        let block = matrix.view(1..3, 1..3);
+        let owned = block.to_matrix();
+
+        assert_eq!(block.inverse(), owned.inverse());
+        assert_eq!(block.lu(), owned.lu());
+        assert_eq!(block.svd(), owned.svd());
+        assert_eq!(
+            matrix.transpose_view().cholesky(),
+            matrix.transpose_view().to_matrix().cholesky()
+        );
+    }
+}
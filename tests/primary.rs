@@ -1,13 +1,20 @@
 #[macro_use]
 extern crate mtrs;
 
-use mtrs::Matrix;
+use mtrs::{Matrix, MatrixError};
 
 #[test]
 fn test_display() {
     let matrix = matrix![(2, 2); 1, 2; 3, 4];
 
     assert_eq!(format!("{}", matrix), "1 2 \n3 4 \n".to_string());
+
+    let uneven = matrix![(2, 3); 1, -20, 3; 400, 5, 6];
+    assert_eq!(format!("{}", uneven), "  1 -20 3 \n400   5 6 \n");
+    assert_eq!(
+        format!("{:.2}", matrix![f64; (1, 2); 0.5, 1]),
+        "0.50 1.00 \n"
+    );
 }
 
 #[test]
@@ -36,6 +43,37 @@ fn test_transpose() {
 
     assert_eq!(matrix, matrix![(2, 2); 1, 3; 2, 4]);
     assert_eq!(matrix2, matrix![(3, 3); 3, 1, 9; 4, 2, 5; 7, 6, 7]);
+
+    let mut rectangular = matrix![(2, 3); 1, 2, 3; 4, 5, 6];
+    rectangular.transpose();
+    assert_eq!(rectangular, matrix![(3, 2); 1, 4; 2, 5; 3, 6]);
+
+    let mut empty: Matrix<i32> = Matrix::zeros(0);
+    empty.transpose();
+    assert_eq!(empty.size(), (0, 0));
+
+    let mut no_rows: Matrix<i32> = Matrix::zeros((0, 3));
+    no_rows.transpose();
+    assert_eq!(no_rows.size(), (3, 0));
+    assert_eq!(no_rows.transposed().size(), (0, 3));
+}
+
+#[test]
+fn test_large_transpose() {
+    // Shapes spanning several tiles, with cycles of many different lengths
+    for &(height, width) in &[(1, 7), (7, 1), (70, 70), (33, 65), (100, 3), (64, 48)] {
+        let matrix = Matrix::from_vec(
+            (height, width),
+            (0..height * width).map(|i| i as u32).collect(),
+        );
+        let mut transposed = matrix.clone();
+        transposed.transpose();
+
+        assert_eq!(transposed.size(), (width, height));
+        assert_eq!(transposed, matrix.transposed());
+        assert_eq!(transposed, matrix.transpose_view().to_matrix());
+        assert_eq!(transposed[(width - 1, 0)], (width - 1) as u32);
+    }
 }
 
 #[test]
@@ -78,6 +116,34 @@ fn test_inverse() {
     assert_eq!(matrix.determinant(), Some(-0.5));
     assert_eq!(
         matrix.inverse().expect("Could not take inverse"),
-        matrix![f32; (2, 2); 2, -3; -2, 2]
+        matrix![f32; (2, 2); 2, 3; 2, 2]
     );
+
+    let matrix = matrix![f64; (3, 3); 0, 2, 1; 1, 1, 0; 3, 0, 1];
+    let product = matrix.clone() * matrix.inverse().expect("Could not take inverse");
+    let identity: Matrix<f64> = Matrix::identity(3);
+
+    for (left, right) in product.as_slice().iter().zip(identity.as_slice()) {
+        assert!((left - right).abs() < 1e-12);
+    }
+}
+
+#[test]
+fn test_inverse_failure() {
+    let non_square = matrix![f64; (2, 3); 1, 2, 3; 4, 5, 6];
+    let singular = matrix![f64; (3, 3); 1, 2, 3; 4, 5, 6; 7, 8, 9];
+    let near_singular = matrix![f64; (2, 2); 1, 1; 1, 1.0 + 1e-17];
+
+    assert_eq!(non_square.inverse(), Err(MatrixError::NotSquare));
+    assert_eq!(singular.inverse(), Err(MatrixError::Singular));
+    assert_eq!(near_singular.inverse(), Err(MatrixError::Singular));
+}
+
+#[test]
+fn test_from_nested_vec() {
+    let matrix = matrix![vec![vec![1, 2, 3], vec![4, 5, 6]]];
+    let empty: Matrix<i32> = matrix![Vec::<Vec<i32>>::new()];
+
+    assert_eq!(matrix, matrix![(2, 3); 1, 2, 3; 4, 5, 6]);
+    assert_eq!(empty.size(), (0, 0));
 }